@@ -0,0 +1,248 @@
+//! Value-conserving exit bundles for Voile Protocol
+//!
+//! A [`Bundle`] pairs a set of input and output exit notes and proves that
+//! value is conserved across them — `sum(inputs) == sum(outputs) + fee` —
+//! without revealing any individual amount. Each note contributes a
+//! homomorphic value commitment `C_v = v*G + r*H`; because the `v*G` terms
+//! cancel when the bundle balances, the net blinding is proven in zero
+//! knowledge with a Schnorr proof of knowledge with respect to `H`.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::Rng;
+use sha3::{Digest, Keccak256};
+use crate::commitment::pedersen_h;
+use crate::{NoteCommitmentRandomness, PedersenCommitment, Result, VoileError};
+
+/// A note's contribution to a bundle: its amount and blinding factor
+pub struct BundleNote {
+    /// The (hidden) amount
+    pub value: u64,
+    /// The blinding factor of the value commitment
+    pub blinding: NoteCommitmentRandomness,
+}
+
+impl BundleNote {
+    /// Create a bundle note from an amount and blinding factor
+    pub fn new(value: u64, blinding: NoteCommitmentRandomness) -> Self {
+        Self { value, blinding }
+    }
+
+    /// The value commitment for this note
+    fn commitment(&self) -> PedersenCommitment {
+        PedersenCommitment::new(self.value, &self.blinding)
+    }
+}
+
+/// A balanced bundle of input and output notes with an explicit public fee
+pub struct Bundle {
+    inputs: Vec<BundleNote>,
+    outputs: Vec<BundleNote>,
+    fee: u64,
+}
+
+impl Bundle {
+    /// Create a bundle from inputs, outputs, and a public fee
+    pub fn new(inputs: Vec<BundleNote>, outputs: Vec<BundleNote>, fee: u64) -> Self {
+        Self { inputs, outputs, fee }
+    }
+
+    /// Produce a zero-knowledge balance proof for this bundle
+    ///
+    /// Returns [`VoileError::ProofGenerationError`] if either side is empty.
+    pub fn prove(&self, domain: &[u8]) -> Result<BundleProof> {
+        if self.inputs.is_empty() || self.outputs.is_empty() {
+            return Err(VoileError::ProofGenerationError(
+                "Bundle must have at least one input and one output".to_string()
+            ));
+        }
+
+        let input_commitments: Vec<PedersenCommitment> = self.inputs.iter().map(|n| n.commitment()).collect();
+        let output_commitments: Vec<PedersenCommitment> = self.outputs.iter().map(|n| n.commitment()).collect();
+
+        // Net blinding z = sum(r_in) - sum(r_out).
+        let mut z = Scalar::ZERO;
+        for n in &self.inputs {
+            z += n.blinding.as_scalar();
+        }
+        for n in &self.outputs {
+            z -= n.blinding.as_scalar();
+        }
+
+        // Schnorr proof of knowledge of z with respect to H.
+        let mut k_bytes = [0u8; 64];
+        rand::thread_rng().fill(&mut k_bytes);
+        let k = Scalar::from_bytes_mod_order_wide(&k_bytes);
+        let announcement = pedersen_h() * k;
+
+        let challenge = compute_challenge(
+            domain,
+            self.fee,
+            &input_commitments,
+            &output_commitments,
+            &announcement,
+        );
+        let s = k + challenge * z;
+
+        Ok(BundleProof {
+            input_commitments: input_commitments.iter().map(|c| c.to_bytes()).collect(),
+            output_commitments: output_commitments.iter().map(|c| c.to_bytes()).collect(),
+            fee: self.fee,
+            announcement: announcement.compress().to_bytes(),
+            response: s.to_bytes(),
+        })
+    }
+}
+
+/// A bundle balance proof, verifiable from public data alone
+pub struct BundleProof {
+    /// Input value commitments (compressed points)
+    pub input_commitments: Vec<[u8; 32]>,
+    /// Output value commitments (compressed points)
+    pub output_commitments: Vec<[u8; 32]>,
+    /// The public fee
+    pub fee: u64,
+    /// Schnorr announcement `R = k*H`
+    pub announcement: [u8; 32],
+    /// Schnorr response `s = k + c*z`
+    pub response: [u8; 32],
+}
+
+impl BundleProof {
+    /// Verify that the bundle conserves value
+    pub fn verify(&self, domain: &[u8]) -> Result<()> {
+        if self.input_commitments.is_empty() || self.output_commitments.is_empty() {
+            return Err(VoileError::ProofVerificationFailed(
+                "Bundle must have at least one input and one output".to_string()
+            ));
+        }
+
+        let inputs = decompress_all(&self.input_commitments)?;
+        let outputs = decompress_all(&self.output_commitments)?;
+
+        let announcement = curve25519_dalek::ristretto::CompressedRistretto(self.announcement)
+            .decompress()
+            .ok_or_else(|| VoileError::ProofVerificationFailed("Invalid announcement".to_string()))?;
+        let s = Scalar::from_canonical_bytes(self.response)
+            .into_option()
+            .ok_or_else(|| VoileError::ProofVerificationFailed("Invalid response scalar".to_string()))?;
+
+        let input_pedersen: Vec<PedersenCommitment> = self
+            .input_commitments
+            .iter()
+            .map(|b| PedersenCommitment::from_bytes(b))
+            .collect::<Result<_>>()?;
+        let output_pedersen: Vec<PedersenCommitment> = self
+            .output_commitments
+            .iter()
+            .map(|b| PedersenCommitment::from_bytes(b))
+            .collect::<Result<_>>()?;
+
+        let challenge = compute_challenge(
+            domain,
+            self.fee,
+            &input_pedersen,
+            &output_pedersen,
+            &announcement,
+        );
+
+        // D = sum(C_in) - sum(C_out) - fee*G; a balanced bundle makes D = z*H.
+        let mut balance = RistrettoPoint::default();
+        for c in &inputs {
+            balance += c;
+        }
+        for c in &outputs {
+            balance -= c;
+        }
+        balance -= RISTRETTO_BASEPOINT_POINT * Scalar::from(self.fee);
+
+        if pedersen_h() * s == announcement + balance * challenge {
+            Ok(())
+        } else {
+            Err(VoileError::ProofVerificationFailed(
+                "Bundle does not balance".to_string()
+            ))
+        }
+    }
+}
+
+/// Recompute the Fiat-Shamir challenge over the full transcript
+///
+/// The fee and note counts are bound in so a bundle cannot be truncated or
+/// re-weighted after the fact.
+fn compute_challenge(
+    domain: &[u8],
+    fee: u64,
+    inputs: &[PedersenCommitment],
+    outputs: &[PedersenCommitment],
+    announcement: &RistrettoPoint,
+) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_bundle_challenge");
+    hasher.update(domain);
+    hasher.update(fee.to_le_bytes());
+    hasher.update((inputs.len() as u64).to_le_bytes());
+    hasher.update((outputs.len() as u64).to_le_bytes());
+    for c in inputs {
+        hasher.update(c.to_bytes());
+    }
+    for c in outputs {
+        hasher.update(c.to_bytes());
+    }
+    hasher.update(announcement.compress().to_bytes());
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// Decompress a list of compressed commitments into group points
+fn decompress_all(commitments: &[[u8; 32]]) -> Result<Vec<RistrettoPoint>> {
+    commitments
+        .iter()
+        .map(|b| {
+            PedersenCommitment::from_bytes(b).map(|c| c.point())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(value: u64, seed: u8) -> BundleNote {
+        BundleNote::new(value, NoteCommitmentRandomness::from_bytes(&[seed; 32]))
+    }
+
+    #[test]
+    fn test_balanced_bundle_verifies() {
+        let bundle = Bundle::new(
+            vec![note(1000, 1), note(500, 2)],
+            vec![note(1400, 3)],
+            100,
+        );
+        let proof = bundle.prove(b"voile_mainnet").unwrap();
+        assert!(proof.verify(b"voile_mainnet").is_ok());
+    }
+
+    #[test]
+    fn test_unbalanced_bundle_fails() {
+        // Outputs + fee (1500) do not equal inputs (1000).
+        let bundle = Bundle::new(vec![note(1000, 1)], vec![note(1400, 3)], 100);
+        let proof = bundle.prove(b"voile_mainnet").unwrap();
+        assert!(proof.verify(b"voile_mainnet").is_err());
+    }
+
+    #[test]
+    fn test_empty_side_rejected() {
+        let bundle = Bundle::new(vec![], vec![note(1, 1)], 0);
+        assert!(bundle.prove(b"voile_mainnet").is_err());
+    }
+
+    #[test]
+    fn test_wrong_domain_fails() {
+        let bundle = Bundle::new(vec![note(1000, 1)], vec![note(900, 3)], 100);
+        let proof = bundle.prove(b"chain_a").unwrap();
+        assert!(proof.verify(b"chain_b").is_err());
+    }
+}
@@ -5,6 +5,12 @@
 //! are used to represent exit notes on-chain without revealing their contents.
 
 use sha3::{Digest, Keccak256};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::Rng;
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId, Signature};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
 use crate::{Result, VoileError};
 
 /// A cryptographic commitment to a value
@@ -88,6 +94,247 @@ impl Commitment {
     }
 }
 
+/// A typed blinding factor (scalar) for algebraic commitments
+///
+/// Wrapping the scalar keeps blinding material distinct from arbitrary bytes
+/// at the type level, mirroring the note-commitment randomness wrappers used
+/// elsewhere in shielded-pool designs.
+#[derive(Clone, Copy)]
+pub struct NoteCommitmentRandomness(Scalar);
+
+impl NoteCommitmentRandomness {
+    /// Sample a fresh random blinding factor
+    pub fn random() -> Self {
+        let mut bytes = [0u8; 64];
+        rand::thread_rng().fill(&mut bytes);
+        Self(Scalar::from_bytes_mod_order_wide(&bytes))
+    }
+
+    /// Build a blinding factor from 32 bytes, reduced modulo the group order
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self(Scalar::from_bytes_mod_order(*bytes))
+    }
+
+    /// The underlying scalar
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+}
+
+/// The second Pedersen generator `H`, derived independently of `G`
+///
+/// Obtained by hashing a fixed domain separator onto the group so that the
+/// discrete log of `H` with respect to `G` is unknown.
+pub(crate) fn pedersen_h() -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<sha2::Sha512>(b"voile_pedersen_generator_H")
+}
+
+/// An additively-homomorphic Pedersen commitment `C = value*G + blinding*H`
+///
+/// Unlike the hash [`Commitment`], Pedersen commitments can be added and
+/// subtracted, so an aggregator can prove that a set of private exit amounts
+/// sums to a public total (`C_total == C_1 + C_2 + ...`) without revealing any
+/// individual amount.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PedersenCommitment {
+    point: RistrettoPoint,
+}
+
+impl PedersenCommitment {
+    /// Commit to `value` under the given blinding factor
+    pub fn new(value: u64, blinding: &NoteCommitmentRandomness) -> Self {
+        let point = RISTRETTO_BASEPOINT_POINT * Scalar::from(value) + pedersen_h() * blinding.as_scalar();
+        Self { point }
+    }
+
+    /// Verify that `value` and `blinding` open this commitment
+    pub fn verify(&self, value: u64, blinding: &NoteCommitmentRandomness) -> bool {
+        *self == Self::new(value, blinding)
+    }
+
+    /// The underlying group point
+    pub(crate) fn point(&self) -> RistrettoPoint {
+        self.point
+    }
+
+    /// Homomorphically add two commitments (`C_a + C_b` commits to `a + b`)
+    pub fn add(&self, other: &PedersenCommitment) -> PedersenCommitment {
+        PedersenCommitment { point: self.point + other.point }
+    }
+
+    /// Homomorphically subtract two commitments (`C_a - C_b` commits to `a - b`)
+    pub fn sub(&self, other: &PedersenCommitment) -> PedersenCommitment {
+        PedersenCommitment { point: self.point - other.point }
+    }
+
+    /// Serialize the commitment to its 32-byte compressed point encoding
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.point.compress().to_bytes()
+    }
+
+    /// Deserialize a commitment from a 32-byte compressed point encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 32 {
+            return Err(VoileError::InvalidCommitment(
+                format!("Expected 32 bytes, got {}", bytes.len())
+            ));
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(bytes);
+        let point = CompressedRistretto(buf).decompress().ok_or_else(|| {
+            VoileError::InvalidCommitment("Not a valid Ristretto point".to_string())
+        })?;
+        Ok(Self { point })
+    }
+}
+
+/// The fixed generator `G` that a note's amount is committed under
+fn note_commitment_amount_generator() -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<sha2::Sha512>(b"voile_cm_generator_amount")
+}
+
+/// Derive the blinding generator for a note's non-amount attributes
+///
+/// Hashing `owner`, `terms`, and `created_at` onto the group gives every
+/// distinct attribute tuple its own generator. Binding the attributes into
+/// the *choice of generator*, rather than into a separate additive term,
+/// keeps the commitment additive in `(amount, blinding)` for two notes that
+/// share the same attributes: an additive term would double when two such
+/// commitments are summed, while the generator is shared and cancels out.
+fn note_commitment_attribute_generator(owner: &[u8; 32], terms: &[u8], created_at: u64) -> RistrettoPoint {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_cm_generator_attrs");
+    hasher.update(owner);
+    hasher.update(terms);
+    hasher.update(created_at.to_le_bytes());
+    RistrettoPoint::hash_from_bytes::<sha2::Sha512>(&hasher.finalize())
+}
+
+/// An additively-homomorphic note commitment suitable for use in a circuit
+///
+/// Unlike the hash [`Commitment`], this commits to a note's `amount` via a
+/// fixed-generator Pedersen commitment, so exit amounts can later be proven
+/// and balanced inside a SNARK without being revealed. The note's other
+/// fields (`owner`, `terms`, `created_at`) are bound in via the choice of
+/// blinding generator rather than as separate point terms, so the
+/// commitment stays additively homomorphic in `amount` for a batch of exits
+/// that share the same attributes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtractedNoteCommitment {
+    point: RistrettoPoint,
+}
+
+impl ExtractedNoteCommitment {
+    /// Commit to a note's amount under the given blinding factor
+    ///
+    /// `cm = amount*G + blinding*H_attr(owner, terms, created_at)`.
+    pub fn compute(
+        amount: u64,
+        owner: &[u8; 32],
+        terms: &[u8],
+        created_at: u64,
+        blinding: &NoteCommitmentRandomness,
+    ) -> Self {
+        let g = note_commitment_amount_generator();
+        let h_attr = note_commitment_attribute_generator(owner, terms, created_at);
+        let point = Scalar::from(amount) * g + blinding.as_scalar() * h_attr;
+        Self { point }
+    }
+
+    /// The on-chain 32-byte encoding (compressed x-coordinate) of the commitment
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.point.compress().to_bytes()
+    }
+
+    /// The full group point, retained for proof construction
+    pub fn point(&self) -> &RistrettoPoint {
+        &self.point
+    }
+
+    /// Homomorphically add two note commitments
+    ///
+    /// Only sums amounts correctly when both commitments share the same
+    /// `owner`, `terms`, and `created_at` (and so the same blinding
+    /// generator); combining commitments with different attributes yields a
+    /// point that opens to neither note.
+    pub fn add(&self, other: &ExtractedNoteCommitment) -> ExtractedNoteCommitment {
+        ExtractedNoteCommitment { point: self.point + other.point }
+    }
+
+    /// Homomorphically subtract two note commitments
+    ///
+    /// See [`add`](Self::add) for the same-attributes requirement.
+    pub fn sub(&self, other: &ExtractedNoteCommitment) -> ExtractedNoteCommitment {
+        ExtractedNoteCommitment { point: self.point - other.point }
+    }
+}
+
+/// Hash a commitment into the secp256k1 message digest used for signing
+///
+/// The message convention is `Keccak256(commitment.as_bytes())`.
+fn commitment_message(commitment: &Commitment) -> Message {
+    let mut hasher = Keccak256::new();
+    hasher.update(commitment.as_bytes());
+    let digest = hasher.finalize();
+    Message::from_digest_slice(&digest).expect("Keccak256 digest is 32 bytes")
+}
+
+/// Sign a commitment with secp256k1 ECDSA, producing a 64-byte low-S signature
+///
+/// The signature authorizes the commitment as having been produced by the
+/// holder of `secret_key`, letting an LP or verifier check note authenticity
+/// before running the heavier exit proof.
+pub fn sign_commitment(commitment: &Commitment, secret_key: &[u8; 32]) -> Result<[u8; 64]> {
+    let secp = Secp256k1::new();
+    let sk = SecretKey::from_slice(secret_key)
+        .map_err(|e| VoileError::InvalidKey(format!("Invalid signing key: {}", e)))?;
+    let mut sig = secp.sign_ecdsa(&commitment_message(commitment), &sk);
+    sig.normalize_s();
+    Ok(sig.serialize_compact())
+}
+
+/// Verify a 64-byte ECDSA signature over a commitment against a public key
+///
+/// Returns [`VoileError::ProofVerificationFailed`] on any mismatch or malformed
+/// input.
+pub fn verify_signature(commitment: &Commitment, signature: &[u8; 64], pubkey: &[u8]) -> Result<()> {
+    let secp = Secp256k1::new();
+    let pk = PublicKey::from_slice(pubkey)
+        .map_err(|e| VoileError::ProofVerificationFailed(format!("Invalid public key: {}", e)))?;
+    let sig = Signature::from_compact(signature)
+        .map_err(|e| VoileError::ProofVerificationFailed(format!("Malformed signature: {}", e)))?;
+    secp.verify_ecdsa(&commitment_message(commitment), &sig, &pk)
+        .map_err(|_| VoileError::ProofVerificationFailed("Signature does not verify".to_string()))
+}
+
+/// Sign a commitment producing a 65-byte recoverable signature (`r || s || recid`)
+///
+/// The trailing recovery id lets a verifier recover the author's public key
+/// from the signature alone via [`recover_signer`].
+pub fn sign_commitment_recoverable(commitment: &Commitment, secret_key: &[u8; 32]) -> Result<[u8; 65]> {
+    let secp = Secp256k1::new();
+    let sk = SecretKey::from_slice(secret_key)
+        .map_err(|e| VoileError::InvalidKey(format!("Invalid signing key: {}", e)))?;
+    let sig = secp.sign_ecdsa_recoverable(&commitment_message(commitment), &sk);
+    let (recid, compact) = sig.serialize_compact();
+    let mut out = [0u8; 65];
+    out[..64].copy_from_slice(&compact);
+    out[64] = recid.to_i32() as u8;
+    Ok(out)
+}
+
+/// Recover the signer's compressed public key from a recoverable signature
+pub fn recover_signer(commitment: &Commitment, signature: &[u8; 65]) -> Result<Vec<u8>> {
+    let secp = Secp256k1::new();
+    let recid = RecoveryId::from_i32(signature[64] as i32)
+        .map_err(|e| VoileError::ProofVerificationFailed(format!("Invalid recovery id: {}", e)))?;
+    let sig = RecoverableSignature::from_compact(&signature[..64], recid)
+        .map_err(|e| VoileError::ProofVerificationFailed(format!("Malformed signature: {}", e)))?;
+    let pk = secp.recover_ecdsa(&commitment_message(commitment), &sig)
+        .map_err(|_| VoileError::ProofVerificationFailed("Could not recover public key".to_string()))?;
+    Ok(pk.serialize().to_vec())
+}
+
 impl std::fmt::Debug for Commitment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Commitment({})", self.to_hex())
@@ -145,4 +392,89 @@ mod tests {
         let result = Commitment::from_bytes(&[0u8; 16]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_pedersen_open_and_verify() {
+        let blinding = NoteCommitmentRandomness::from_bytes(&[7u8; 32]);
+        let commitment = PedersenCommitment::new(1000, &blinding);
+
+        assert!(commitment.verify(1000, &blinding));
+        assert!(!commitment.verify(1001, &blinding));
+    }
+
+    #[test]
+    fn test_pedersen_homomorphic_addition() {
+        let r1 = NoteCommitmentRandomness::from_bytes(&[1u8; 32]);
+        let r2 = NoteCommitmentRandomness::from_bytes(&[2u8; 32]);
+        let r_sum = NoteCommitmentRandomness(r1.as_scalar() + r2.as_scalar());
+
+        let c1 = PedersenCommitment::new(400, &r1);
+        let c2 = PedersenCommitment::new(600, &r2);
+
+        // Sum of the commitments opens to the sum of the values.
+        assert_eq!(c1.add(&c2), PedersenCommitment::new(1000, &r_sum));
+        assert_eq!(c1.add(&c2).sub(&c2), c1);
+    }
+
+    #[test]
+    fn test_commitment_signature_roundtrip() {
+        let secp = Secp256k1::new();
+        let sk = [11u8; 32];
+        let pk = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&sk).unwrap()).serialize();
+
+        let commitment = Commitment::new(b"exit_note", &[5u8; 32]);
+        let sig = sign_commitment(&commitment, &sk).unwrap();
+
+        assert!(verify_signature(&commitment, &sig, &pk).is_ok());
+
+        // A different commitment must not verify under the same signature.
+        let other = Commitment::new(b"other_note", &[5u8; 32]);
+        assert!(verify_signature(&other, &sig, &pk).is_err());
+    }
+
+    #[test]
+    fn test_commitment_signature_recovery() {
+        let secp = Secp256k1::new();
+        let sk = [22u8; 32];
+        let pk = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&sk).unwrap()).serialize();
+
+        let commitment = Commitment::new(b"recoverable", &[6u8; 32]);
+        let sig = sign_commitment_recoverable(&commitment, &sk).unwrap();
+
+        let recovered = recover_signer(&commitment, &sig).unwrap();
+        assert_eq!(recovered, pk.to_vec());
+    }
+
+    #[test]
+    fn test_extracted_commitment_homomorphic_in_amount() {
+        let owner = [3u8; 32];
+        let terms = b"standard";
+        let r1 = NoteCommitmentRandomness::from_bytes(&[1u8; 32]);
+        let r2 = NoteCommitmentRandomness::from_bytes(&[2u8; 32]);
+        let r_sum = NoteCommitmentRandomness(r1.as_scalar() + r2.as_scalar());
+
+        let c1 = ExtractedNoteCommitment::compute(400, &owner, terms, 0, &r1);
+        let c2 = ExtractedNoteCommitment::compute(600, &owner, terms, 0, &r2);
+        let c_total = ExtractedNoteCommitment::compute(1000, &owner, terms, 0, &r_sum);
+
+        // With identical non-amount attributes, summing amounts adds the commitments.
+        assert_eq!(c1.add(&c2), c_total);
+    }
+
+    #[test]
+    fn test_extracted_commitment_binds_attributes() {
+        let r = NoteCommitmentRandomness::from_bytes(&[5u8; 32]);
+        let a = ExtractedNoteCommitment::compute(100, &[1u8; 32], b"standard", 10, &r);
+        let b = ExtractedNoteCommitment::compute(100, &[2u8; 32], b"standard", 10, &r);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_pedersen_serialization() {
+        let blinding = NoteCommitmentRandomness::from_bytes(&[42u8; 32]);
+        let commitment = PedersenCommitment::new(12345, &blinding);
+
+        let recovered = PedersenCommitment::from_bytes(&commitment.to_bytes()).unwrap();
+        assert_eq!(commitment, recovered);
+    }
 }
@@ -0,0 +1,398 @@
+//! Anonymous authorization credentials for Voile Protocol
+//!
+//! Holding `owner_secret` is enough to mint an [`ExitProof`](crate::proof::ExitProof),
+//! but a bridge operator or DAO may want to gate *who* is allowed to exit
+//! without learning *which* note is being exited. This module adds a credential
+//! layer: an [`Issuer`] blind-signs a commitment to an exit note, the user
+//! unblinds the signature, and later proves — in zero knowledge, bound to the
+//! exit proof — that it holds a valid issuer signature over the same
+//! commitment. The blinding makes the issued credential unlinkable to its
+//! signing session, and the proof of knowledge keeps the signature itself
+//! hidden so exits cannot be linked to one another.
+//!
+//! The construction is a blind Schnorr signature over ristretto255 (the same
+//! group used for value commitments) together with a Schnorr proof of knowledge
+//! of the signature scalar.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::Rng;
+use sha3::{Digest, Keccak256};
+use crate::{Result, VoileError};
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn point_bytes(p: &RistrettoPoint) -> [u8; 32] {
+    p.compress().to_bytes()
+}
+
+fn parse_point(bytes: &[u8; 32]) -> Result<RistrettoPoint> {
+    CompressedRistretto(*bytes)
+        .decompress()
+        .ok_or_else(|| VoileError::InvalidKey("Invalid credential point".to_string()))
+}
+
+fn parse_scalar(bytes: &[u8; 32]) -> Result<Scalar> {
+    Scalar::from_canonical_bytes(*bytes)
+        .into_option()
+        .ok_or_else(|| VoileError::InvalidKey("Invalid credential scalar".to_string()))
+}
+
+/// Map a commitment's bytes to the message scalar the credential signs
+fn message_scalar(commitment: &[u8]) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_cred_msg");
+    hasher.update(commitment);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+/// The Schnorr challenge `c = H(R || m)` of an issued signature
+fn sig_challenge(r_point: &RistrettoPoint, message: &Scalar) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_cred_sig");
+    hasher.update(r_point.compress().to_bytes());
+    hasher.update(message.to_bytes());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+/// An issuer's public verification key
+#[derive(Clone, Copy)]
+pub struct IssuerPublicKey {
+    point: RistrettoPoint,
+}
+
+impl IssuerPublicKey {
+    /// Serialize the public key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        point_bytes(&self.point)
+    }
+
+    /// Deserialize a public key
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        Ok(Self { point: parse_point(bytes)? })
+    }
+}
+
+/// A credential issuer that blind-signs exit-note commitments
+pub struct Issuer {
+    secret: Scalar,
+    public: RistrettoPoint,
+}
+
+impl Issuer {
+    /// Create an issuer from 32 bytes of secret key material
+    pub fn from_secret(secret: &[u8; 32]) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_issuer_secret");
+        hasher.update(secret);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        let secret = Scalar::from_bytes_mod_order(out);
+        Self { secret, public: RISTRETTO_BASEPOINT_POINT * secret }
+    }
+
+    /// The issuer's public verification key
+    pub fn public_key(&self) -> IssuerPublicKey {
+        IssuerPublicKey { point: self.public }
+    }
+
+    /// First move of blind signing: produce a one-time nonce commitment `R'`
+    ///
+    /// The returned [`SigningNonce`] must be kept secret and passed to
+    /// [`blind_sign`](Self::blind_sign); it may be used only once.
+    pub fn commit_nonce(&self) -> (SigningNonce, [u8; 32]) {
+        let r = random_scalar();
+        let r_point = RISTRETTO_BASEPOINT_POINT * r;
+        (SigningNonce { r }, point_bytes(&r_point))
+    }
+
+    /// Blind-sign a blinded challenge, returning the blinded response `s'`
+    pub fn blind_sign(&self, nonce: &SigningNonce, blinded_challenge: &[u8; 32]) -> Result<[u8; 32]> {
+        let e_blinded = parse_scalar(blinded_challenge)?;
+        let s = nonce.r + e_blinded * self.secret;
+        Ok(s.to_bytes())
+    }
+}
+
+/// An issuer's one-time signing nonce
+pub struct SigningNonce {
+    r: Scalar,
+}
+
+/// A pending credential request, holding the user's blinding factors
+pub struct CredentialRequest {
+    message: Scalar,
+    alpha: Scalar,
+    r_point: RistrettoPoint,
+    challenge: Scalar,
+}
+
+impl CredentialRequest {
+    /// Begin a credential request over `commitment` against `R'`
+    ///
+    /// Returns the request together with the blinded challenge `e'` to send to
+    /// the issuer.
+    pub fn new(
+        issuer: &IssuerPublicKey,
+        r_prime: &[u8; 32],
+        commitment: &[u8],
+    ) -> Result<(Self, [u8; 32])> {
+        let r_prime = parse_point(r_prime)?;
+        let message = message_scalar(commitment);
+
+        let alpha = random_scalar();
+        let beta = random_scalar();
+        let r_point = r_prime + RISTRETTO_BASEPOINT_POINT * alpha + issuer.point * beta;
+        let challenge = sig_challenge(&r_point, &message);
+        let blinded = challenge + beta;
+
+        Ok((Self { message, alpha, r_point, challenge }, blinded.to_bytes()))
+    }
+
+    /// Finish the request by unblinding the issuer's response into a credential
+    pub fn unblind(self, issuer: &IssuerPublicKey, blinded_response: &[u8; 32]) -> Result<Credential> {
+        let s_blinded = parse_scalar(blinded_response)?;
+        let s = s_blinded + self.alpha;
+
+        let credential = Credential {
+            r_point: self.r_point,
+            challenge: self.challenge,
+            s,
+            message: self.message,
+        };
+        // Reject a malformed issuance eagerly.
+        if !credential.is_valid(issuer) {
+            return Err(VoileError::InvalidKey(
+                "Issuer returned an invalid credential".to_string()
+            ));
+        }
+        Ok(credential)
+    }
+}
+
+/// An unblinded anonymous credential: a Schnorr signature `(R, e, s)` on a
+/// commitment's message scalar, verifiable under the issuer's public key
+pub struct Credential {
+    r_point: RistrettoPoint,
+    challenge: Scalar,
+    s: Scalar,
+    message: Scalar,
+}
+
+impl Credential {
+    /// Check the credential is a valid issuer signature
+    fn is_valid(&self, issuer: &IssuerPublicKey) -> bool {
+        // e must bind R and the message, and s*G == R + e*X.
+        if sig_challenge(&self.r_point, &self.message) != self.challenge {
+            return false;
+        }
+        RISTRETTO_BASEPOINT_POINT * self.s == self.r_point + issuer.point * self.challenge
+    }
+}
+
+/// A zero-knowledge proof that the prover holds a valid issuer credential over
+/// the exit proof's commitment, bound to that proof's transcript
+#[derive(Clone)]
+pub struct CredentialProof {
+    /// The signature nonce point `R`
+    r_point: [u8; 32],
+    /// The signature challenge `e = H(R || m)`
+    challenge: [u8; 32],
+    /// Schnorr announcement `T = k*G`
+    announcement: [u8; 32],
+    /// Schnorr response `z = k + c*s`
+    response: [u8; 32],
+}
+
+/// Fiat-Shamir challenge binding the credential proof to the exit transcript
+fn proof_challenge(
+    domain: &[u8; 32],
+    r_point: &[u8; 32],
+    challenge: &[u8; 32],
+    commitment: &[u8],
+    nullifier: &[u8; 32],
+    exit_announcement: &[u8; 32],
+    announcement: &[u8; 32],
+) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_cred_challenge");
+    hasher.update(domain);
+    hasher.update(r_point);
+    hasher.update(challenge);
+    hasher.update(commitment);
+    hasher.update(nullifier);
+    hasher.update(exit_announcement);
+    hasher.update(announcement);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+/// Prove knowledge of a credential, bound to an exit proof's transcript
+pub fn prove(
+    domain: &[u8; 32],
+    credential: &Credential,
+    commitment: &[u8],
+    nullifier: &[u8; 32],
+    exit_announcement: &[u8; 32],
+) -> CredentialProof {
+    let k = random_scalar();
+    let announcement = RISTRETTO_BASEPOINT_POINT * k;
+
+    let r_point = point_bytes(&credential.r_point);
+    let challenge = credential.challenge.to_bytes();
+    let announcement_bytes = point_bytes(&announcement);
+
+    let c = proof_challenge(
+        domain,
+        &r_point,
+        &challenge,
+        commitment,
+        nullifier,
+        exit_announcement,
+        &announcement_bytes,
+    );
+    let z = k + c * credential.s;
+
+    CredentialProof {
+        r_point,
+        challenge,
+        announcement: announcement_bytes,
+        response: z.to_bytes(),
+    }
+}
+
+/// Verify a credential proof against the issuer key and exit transcript
+pub fn verify(
+    domain: &[u8; 32],
+    issuer: &IssuerPublicKey,
+    proof: &CredentialProof,
+    commitment: &[u8],
+    nullifier: &[u8; 32],
+    exit_announcement: &[u8; 32],
+) -> Result<()> {
+    let r_point = parse_point(&proof.r_point)?;
+    let e = parse_scalar(&proof.challenge)?;
+    let announcement = parse_point(&proof.announcement)?;
+    let z = parse_scalar(&proof.response)?;
+
+    // The credential must be a valid issuer signature on this commitment: the
+    // challenge binds R and the message, and P = R + e*X is the public point
+    // whose discrete log is the signature scalar s.
+    let message = message_scalar(commitment);
+    if sig_challenge(&r_point, &message) != e {
+        return Err(VoileError::ProofVerificationFailed(
+            "Credential challenge does not bind the commitment".to_string()
+        ));
+    }
+    let p = r_point + issuer.point * e;
+
+    let c = proof_challenge(
+        domain,
+        &proof.r_point,
+        &proof.challenge,
+        commitment,
+        nullifier,
+        exit_announcement,
+        &proof.announcement,
+    );
+
+    if RISTRETTO_BASEPOINT_POINT * z == announcement + p * c {
+        Ok(())
+    } else {
+        Err(VoileError::ProofVerificationFailed(
+            "Credential proof of knowledge failed".to_string()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: [u8; 32] = [9u8; 32];
+
+    fn issue(issuer: &Issuer, commitment: &[u8]) -> Credential {
+        let pk = issuer.public_key();
+        let (nonce, r_prime) = issuer.commit_nonce();
+        let (request, blinded) = CredentialRequest::new(&pk, &r_prime, commitment).unwrap();
+        let s_prime = issuer.blind_sign(&nonce, &blinded).unwrap();
+        request.unblind(&pk, &s_prime).unwrap()
+    }
+
+    #[test]
+    fn test_blind_issuance_produces_valid_credential() {
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let credential = issue(&issuer, b"commitment-bytes");
+        assert!(credential.is_valid(&issuer.public_key()));
+    }
+
+    #[test]
+    fn test_credential_proof_verifies() {
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let commitment = b"commitment-bytes";
+        let credential = issue(&issuer, commitment);
+
+        let nullifier = [3u8; 32];
+        let exit_announcement = [4u8; 32];
+        let proof = prove(&DOMAIN, &credential, commitment, &nullifier, &exit_announcement);
+
+        assert!(verify(
+            &DOMAIN,
+            &issuer.public_key(),
+            &proof,
+            commitment,
+            &nullifier,
+            &exit_announcement,
+        ).is_ok());
+    }
+
+    #[test]
+    fn test_credential_proof_rejected_under_wrong_issuer() {
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let other = Issuer::from_secret(&[2u8; 32]);
+        let commitment = b"commitment-bytes";
+        let credential = issue(&issuer, commitment);
+
+        let nullifier = [3u8; 32];
+        let exit_announcement = [4u8; 32];
+        let proof = prove(&DOMAIN, &credential, commitment, &nullifier, &exit_announcement);
+
+        assert!(verify(
+            &DOMAIN,
+            &other.public_key(),
+            &proof,
+            commitment,
+            &nullifier,
+            &exit_announcement,
+        ).is_err());
+    }
+
+    #[test]
+    fn test_credential_proof_bound_to_transcript() {
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let commitment = b"commitment-bytes";
+        let credential = issue(&issuer, commitment);
+
+        let proof = prove(&DOMAIN, &credential, commitment, &[3u8; 32], &[4u8; 32]);
+
+        // Presenting the proof against a different exit announcement fails.
+        assert!(verify(
+            &DOMAIN,
+            &issuer.public_key(),
+            &proof,
+            commitment,
+            &[3u8; 32],
+            &[5u8; 32],
+        ).is_err());
+    }
+}
@@ -5,12 +5,157 @@
 //! the commitment to the encrypted data appears on-chain.
 
 use sha3::{Digest, Keccak256};
+use sha2::Sha256;
 use rand::Rng;
+use secp256k1::{ecdh, PublicKey, Secp256k1, SecretKey};
 use crate::{Result, VoileError};
 
 /// Size of the encryption key in bytes
 pub const KEY_SIZE: usize = 32;
 
+/// Size of the authentication tag appended to each ciphertext, in bytes
+pub const TAG_SIZE: usize = 32;
+
+/// Default scrypt cost parameter `log2(n)`
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 15;
+/// Default scrypt block-size parameter `r`
+pub const DEFAULT_SCRYPT_R: u32 = 8;
+/// Default scrypt parallelism parameter `p`
+pub const DEFAULT_SCRYPT_P: u32 = 1;
+/// Default PBKDF2-HMAC-SHA256 iteration count
+pub const DEFAULT_PBKDF2_ROUNDS: u32 = 10_240;
+
+/// Password-stretching algorithm recorded in a [`KeyFile`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KdfAlgorithm {
+    /// scrypt with the given `log2(n)`, `r`, and `p` parameters
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with the given iteration count
+    Pbkdf2 { rounds: u32 },
+}
+
+impl KdfAlgorithm {
+    /// Algorithm tag byte used in the serialized key file
+    fn tag(&self) -> u8 {
+        match self {
+            KdfAlgorithm::Scrypt { .. } => 0,
+            KdfAlgorithm::Pbkdf2 { .. } => 1,
+        }
+    }
+}
+
+/// A reproducible description of how a key was derived from a password
+///
+/// Storing the salt and KDF parameters lets a derived key be reconstructed
+/// across sessions from the same passphrase, following the ethstore keystore
+/// layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyFile {
+    /// Random salt mixed into the KDF
+    pub salt: Vec<u8>,
+    /// Algorithm and its parameters
+    pub algorithm: KdfAlgorithm,
+}
+
+impl KeyFile {
+    /// Derive the 32-byte key described by this key file from `password`
+    pub fn derive(&self, password: &[u8]) -> Result<EncryptionKey> {
+        let mut key = [0u8; KEY_SIZE];
+        match self.algorithm {
+            KdfAlgorithm::Scrypt { log_n, r, p } => {
+                validate_scrypt_params(log_n, r, p)?;
+                let params = scrypt::Params::new(log_n, r, p, KEY_SIZE)
+                    .map_err(|e| VoileError::InvalidKey(format!("Invalid scrypt parameters: {}", e)))?;
+                scrypt::scrypt(password, &self.salt, &params, &mut key)
+                    .map_err(|e| VoileError::InvalidKey(format!("scrypt failed: {}", e)))?;
+            }
+            KdfAlgorithm::Pbkdf2 { rounds } => {
+                pbkdf2::pbkdf2_hmac::<Sha256>(password, &self.salt, rounds, &mut key);
+            }
+        }
+        Ok(EncryptionKey { key })
+    }
+
+    /// Serialize the key file: `algorithm_tag (1) || params || salt_len (2) || salt`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.algorithm.tag()];
+        match self.algorithm {
+            KdfAlgorithm::Scrypt { log_n, r, p } => {
+                bytes.push(log_n);
+                bytes.extend_from_slice(&r.to_le_bytes());
+                bytes.extend_from_slice(&p.to_le_bytes());
+            }
+            KdfAlgorithm::Pbkdf2 { rounds } => {
+                bytes.extend_from_slice(&rounds.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&(self.salt.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes
+    }
+
+    /// Deserialize a key file produced by [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.is_empty() {
+            return Err(VoileError::InvalidKey("Empty key file".to_string()));
+        }
+        let short = || VoileError::InvalidKey("Key file truncated".to_string());
+        let (algorithm, rest) = match bytes[0] {
+            0 => {
+                if bytes.len() < 10 {
+                    return Err(short());
+                }
+                let log_n = bytes[1];
+                let r = u32::from_le_bytes(bytes[2..6].try_into().unwrap());
+                let p = u32::from_le_bytes(bytes[6..10].try_into().unwrap());
+                (KdfAlgorithm::Scrypt { log_n, r, p }, &bytes[10..])
+            }
+            1 => {
+                if bytes.len() < 5 {
+                    return Err(short());
+                }
+                let rounds = u32::from_le_bytes(bytes[1..5].try_into().unwrap());
+                (KdfAlgorithm::Pbkdf2 { rounds }, &bytes[5..])
+            }
+            other => {
+                return Err(VoileError::InvalidKey(format!("Unknown KDF tag: {}", other)));
+            }
+        };
+
+        if rest.len() < 2 {
+            return Err(short());
+        }
+        let salt_len = u16::from_le_bytes(rest[0..2].try_into().unwrap()) as usize;
+        if rest.len() < 2 + salt_len {
+            return Err(short());
+        }
+        let salt = rest[2..2 + salt_len].to_vec();
+        Ok(Self { salt, algorithm })
+    }
+}
+
+/// Validate scrypt parameters before handing them to the KDF
+///
+/// Mirrors the range checks in ethstore: `log2(n)` must stay below `r * 16` and
+/// `p` must not exceed `(2^31 - 1) * 32 / (128 * r)`.
+fn validate_scrypt_params(log_n: u8, r: u32, p: u32) -> Result<()> {
+    if r == 0 || p == 0 {
+        return Err(VoileError::InvalidKey("scrypt r and p must be non-zero".to_string()));
+    }
+    if (log_n as u32) >= r * 16 {
+        return Err(VoileError::InvalidKey(
+            format!("scrypt log_n ({}) must be < r*16 ({})", log_n, r * 16)
+        ));
+    }
+    let p_max = ((1u64 << 31) - 1) * 32 / (128 * r as u64);
+    if p as u64 > p_max {
+        return Err(VoileError::InvalidKey(
+            format!("scrypt p ({}) exceeds maximum {}", p, p_max)
+        ));
+    }
+    Ok(())
+}
+
 /// Encryption key for exit notes
 #[derive(Clone)]
 pub struct EncryptionKey {
@@ -49,11 +194,69 @@ impl EncryptionKey {
         &self.key
     }
 
-    /// Derive a nonce from a counter value
-    fn derive_nonce(&self, counter: u64) -> [u8; 32] {
+    /// Derive an encryption key from a memorized password using scrypt
+    ///
+    /// Uses the default cost parameters (n = 2^15, r = 8, p = 1). The returned
+    /// [`KeyFile`] records the salt and parameters so the same key can be
+    /// reproduced later via [`KeyFile::derive`].
+    ///
+    /// # Returns
+    /// The derived key together with the key file describing its derivation,
+    /// or [`VoileError::InvalidKey`] if the parameters are out of range.
+    pub fn from_password(password: &[u8], salt: &[u8]) -> Result<(Self, KeyFile)> {
+        let key_file = KeyFile {
+            salt: salt.to_vec(),
+            algorithm: KdfAlgorithm::Scrypt {
+                log_n: DEFAULT_SCRYPT_LOG_N,
+                r: DEFAULT_SCRYPT_R,
+                p: DEFAULT_SCRYPT_P,
+            },
+        };
+        let key = key_file.derive(password)?;
+        Ok((key, key_file))
+    }
+
+    /// Derive an encryption key from a password using PBKDF2-HMAC-SHA256
+    ///
+    /// A fallback for environments where scrypt's memory cost is undesirable;
+    /// defaults to [`DEFAULT_PBKDF2_ROUNDS`] iterations.
+    pub fn from_password_pbkdf2(password: &[u8], salt: &[u8]) -> Result<(Self, KeyFile)> {
+        let key_file = KeyFile {
+            salt: salt.to_vec(),
+            algorithm: KdfAlgorithm::Pbkdf2 { rounds: DEFAULT_PBKDF2_ROUNDS },
+        };
+        let key = key_file.derive(password)?;
+        Ok((key, key_file))
+    }
+
+    /// Derive the two sub-keys used by the AEAD construction
+    ///
+    /// Returns `(enc_key, mac_key)` where `enc_key = H(key || "enc")` keys the
+    /// keystream and `mac_key = H(key || "mac")` keys the authentication tag.
+    /// Separating the sub-keys keeps the confidentiality and integrity domains
+    /// independent.
+    fn subkeys(&self) -> ([u8; 32], [u8; 32]) {
+        let mut enc = [0u8; 32];
+        let mut mac = [0u8; 32];
+
         let mut hasher = Keccak256::new();
-        hasher.update(b"voile_nonce");
         hasher.update(self.key);
+        hasher.update(b"enc");
+        enc.copy_from_slice(&hasher.finalize());
+
+        let mut hasher = Keccak256::new();
+        hasher.update(self.key);
+        hasher.update(b"mac");
+        mac.copy_from_slice(&hasher.finalize());
+
+        (enc, mac)
+    }
+
+    /// Derive a nonce from the encryption sub-key and a counter value
+    fn derive_nonce(enc_key: &[u8; 32], counter: u64) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_nonce");
+        hasher.update(enc_key);
         hasher.update(counter.to_le_bytes());
         let result = hasher.finalize();
         let mut nonce = [0u8; 32];
@@ -61,18 +264,18 @@ impl EncryptionKey {
         nonce
     }
 
-    /// Derive a keystream for XOR encryption
-    fn derive_keystream(&self, nonce: &[u8; 32], length: usize) -> Vec<u8> {
+    /// Derive a keystream for XOR encryption from the encryption sub-key
+    fn derive_keystream(enc_key: &[u8; 32], nonce: &[u8; 32], length: usize) -> Vec<u8> {
         let mut keystream = Vec::with_capacity(length);
         let mut block_counter = 0u64;
-        
+
         while keystream.len() < length {
             let mut hasher = Keccak256::new();
-            hasher.update(self.key);
+            hasher.update(enc_key);
             hasher.update(nonce);
             hasher.update(block_counter.to_le_bytes());
             let block = hasher.finalize();
-            
+
             for byte in block.iter() {
                 if keystream.len() >= length {
                     break;
@@ -81,14 +284,94 @@ impl EncryptionKey {
             }
             block_counter += 1;
         }
-        
+
         keystream
     }
 }
 
+/// Default memo bucket size, matching Zcash's fixed 512-byte memo field
+pub const DEFAULT_MEMO_SIZE: usize = 512;
+
+/// Number of bytes reserved for the length prefix in a padded memo
+const MEMO_LEN_PREFIX: usize = 4;
+
+/// Pad `plaintext` into a fixed-size bucket so ciphertext length leaks nothing
+///
+/// Layout: `length (4 LE) || plaintext || zero padding` filled to `bucket`
+/// bytes. Every memo of the same bucket size produces an identically sized
+/// ciphertext regardless of its contents. Returns
+/// [`VoileError::EncryptionError`] if the plaintext does not fit the bucket.
+pub fn pad_to_bucket(plaintext: &[u8], bucket: usize) -> Result<Vec<u8>> {
+    if bucket < MEMO_LEN_PREFIX || plaintext.len() > bucket - MEMO_LEN_PREFIX {
+        return Err(VoileError::EncryptionError(
+            format!("Plaintext of {} bytes does not fit memo bucket {}", plaintext.len(), bucket)
+        ));
+    }
+    let mut padded = vec![0u8; bucket];
+    padded[0..MEMO_LEN_PREFIX].copy_from_slice(&(plaintext.len() as u32).to_le_bytes());
+    padded[MEMO_LEN_PREFIX..MEMO_LEN_PREFIX + plaintext.len()].copy_from_slice(plaintext);
+    Ok(padded)
+}
+
+/// Strip the padding applied by [`pad_to_bucket`], validating the length prefix
+pub fn unpad_from_bucket(padded: &[u8]) -> Result<Vec<u8>> {
+    if padded.len() < MEMO_LEN_PREFIX {
+        return Err(VoileError::DecryptionError("Padded memo too short".to_string()));
+    }
+    let len = u32::from_le_bytes(padded[0..MEMO_LEN_PREFIX].try_into().unwrap()) as usize;
+    if len > padded.len() - MEMO_LEN_PREFIX {
+        return Err(VoileError::DecryptionError(
+            "Memo length prefix exceeds bucket".to_string()
+        ));
+    }
+    Ok(padded[MEMO_LEN_PREFIX..MEMO_LEN_PREFIX + len].to_vec())
+}
+
+/// Compute the authentication tag `T = H(mac_key || counter_le || ad || ciphertext)`
+fn compute_tag(mac_key: &[u8; 32], counter: u64, associated_data: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(mac_key);
+    hasher.update(counter.to_le_bytes());
+    hasher.update(associated_data);
+    hasher.update(ciphertext);
+    let mut tag = [0u8; 32];
+    tag.copy_from_slice(&hasher.finalize());
+    tag
+}
+
+/// ECIES key-derivation: `H(shared_x || ephemeral_pubkey)`
+///
+/// Computes the ECDH shared point between `point` and `scalar`, takes its
+/// x-coordinate, and binds the ephemeral public key into the hash so the
+/// derived symmetric key is tied to this envelope.
+fn ecies_kdf(point: &PublicKey, scalar: &SecretKey, ephemeral_pub: &[u8]) -> EncryptionKey {
+    let shared = ecdh::shared_secret_point(point, scalar);
+    let mut hasher = Keccak256::new();
+    hasher.update(&shared[..32]); // x-coordinate
+    hasher.update(ephemeral_pub);
+    let mut key = [0u8; KEY_SIZE];
+    key.copy_from_slice(&hasher.finalize());
+    EncryptionKey { key }
+}
+
+/// Constant-time equality over two 32-byte tags
+///
+/// Accumulates the XOR of every byte pair and branches only on the final
+/// result, so the comparison does not leak how many leading bytes matched.
+fn tags_equal(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
 /// An encrypted exit note
 ///
-/// Contains the ciphertext and counter needed for decryption.
+/// Contains the ciphertext, the counter used for nonce derivation, and an
+/// authentication tag binding both the ciphertext and any associated data to
+/// the key. The encryption is an encrypt-then-MAC AEAD: a wrong key or any
+/// tampering with the on-chain ciphertext is detected on `decrypt`.
 /// Only the commitment to this encrypted data appears on-chain.
 #[derive(Clone)]
 pub struct EncryptedNote {
@@ -96,48 +379,79 @@ pub struct EncryptedNote {
     ciphertext: Vec<u8>,
     /// Counter used for nonce derivation
     counter: u64,
+    /// Authentication tag over the counter, associated data, and ciphertext
+    tag: [u8; TAG_SIZE],
+    /// Ephemeral public key for key-agreement scanning, when the note was
+    /// encrypted to a viewing key rather than a pre-shared symmetric key
+    ephemeral_pubkey: Option<[u8; 33]>,
 }
 
 impl EncryptedNote {
     /// Encrypt plaintext data using the provided key
     ///
+    /// The plaintext is encrypted with the keystream derived from the `"enc"`
+    /// sub-key, then an authentication tag is computed over the counter, the
+    /// `associated_data`, and the ciphertext using the `"mac"` sub-key. Binding
+    /// `associated_data` (for example the on-chain [`Commitment`](crate::Commitment))
+    /// prevents an attacker from pairing a valid ciphertext with a different
+    /// commitment.
+    ///
     /// # Arguments
     /// * `key` - The encryption key
     /// * `plaintext` - The data to encrypt
+    /// * `associated_data` - Extra context bound to the ciphertext (may be empty)
     ///
     /// # Returns
-    /// A new EncryptedNote containing the ciphertext
-    pub fn encrypt(key: &EncryptionKey, plaintext: &[u8]) -> Self {
+    /// A new EncryptedNote containing the ciphertext and its tag
+    pub fn encrypt(key: &EncryptionKey, plaintext: &[u8], associated_data: &[u8]) -> Self {
+        let (enc_key, mac_key) = key.subkeys();
         let counter = rand::thread_rng().gen();
-        let nonce = key.derive_nonce(counter);
-        let keystream = key.derive_keystream(&nonce, plaintext.len());
-        
+        let nonce = EncryptionKey::derive_nonce(&enc_key, counter);
+        let keystream = EncryptionKey::derive_keystream(&enc_key, &nonce, plaintext.len());
+
         let ciphertext: Vec<u8> = plaintext
             .iter()
             .zip(keystream.iter())
             .map(|(p, k)| p ^ k)
             .collect();
-        
-        Self { ciphertext, counter }
+
+        let tag = compute_tag(&mac_key, counter, associated_data, &ciphertext);
+
+        Self { ciphertext, counter, tag, ephemeral_pubkey: None }
     }
 
     /// Decrypt the note using the provided key
     ///
+    /// Recomputes the authentication tag and compares it against the stored tag
+    /// in constant time before recovering any plaintext. Any key mismatch or
+    /// corruption of the ciphertext, counter, associated data, or tag yields a
+    /// [`VoileError::DecryptionError`].
+    ///
     /// # Arguments
     /// * `key` - The encryption key
+    /// * `associated_data` - The same associated data supplied at encryption
     ///
     /// # Returns
     /// Result containing the decrypted plaintext or an error
-    pub fn decrypt(&self, key: &EncryptionKey) -> Result<Vec<u8>> {
-        let nonce = key.derive_nonce(self.counter);
-        let keystream = key.derive_keystream(&nonce, self.ciphertext.len());
-        
+    pub fn decrypt(&self, key: &EncryptionKey, associated_data: &[u8]) -> Result<Vec<u8>> {
+        let (enc_key, mac_key) = key.subkeys();
+
+        let expected_tag = compute_tag(&mac_key, self.counter, associated_data, &self.ciphertext);
+        if !tags_equal(&expected_tag, &self.tag) {
+            return Err(VoileError::DecryptionError(
+                "Authentication tag mismatch".to_string()
+            ));
+        }
+
+        let nonce = EncryptionKey::derive_nonce(&enc_key, self.counter);
+        let keystream = EncryptionKey::derive_keystream(&enc_key, &nonce, self.ciphertext.len());
+
         let plaintext: Vec<u8> = self.ciphertext
             .iter()
             .zip(keystream.iter())
             .map(|(c, k)| c ^ k)
             .collect();
-        
+
         Ok(plaintext)
     }
 
@@ -146,30 +460,160 @@ impl EncryptedNote {
         &self.ciphertext
     }
 
+    /// Get the authentication tag
+    pub fn tag(&self) -> &[u8; TAG_SIZE] {
+        &self.tag
+    }
+
+    /// Encrypt `plaintext` after padding it to a fixed-size memo bucket
+    ///
+    /// All notes padded to the same `bucket` produce identically sized
+    /// ciphertexts, hiding the length of the underlying exit terms. Use
+    /// [`DEFAULT_MEMO_SIZE`] unless a caller needs a larger bucket.
+    pub fn encrypt_padded(
+        key: &EncryptionKey,
+        plaintext: &[u8],
+        associated_data: &[u8],
+        bucket: usize,
+    ) -> Result<Self> {
+        let padded = pad_to_bucket(plaintext, bucket)?;
+        Ok(Self::encrypt(key, &padded, associated_data))
+    }
+
+    /// Decrypt a memo encrypted with [`encrypt_padded`](Self::encrypt_padded)
+    ///
+    /// Strips the padding and validates the recovered length prefix.
+    pub fn decrypt_padded(&self, key: &EncryptionKey, associated_data: &[u8]) -> Result<Vec<u8>> {
+        let padded = self.decrypt(key, associated_data)?;
+        unpad_from_bucket(&padded)
+    }
+
+    /// Encrypt plaintext directly to a recipient's secp256k1 public key (ECIES)
+    ///
+    /// This is a hybrid scheme that needs no pre-shared symmetric key. An
+    /// ephemeral keypair is generated, an ECDH is performed against
+    /// `recipient_pub`, and the shared x-coordinate is run through a KDF
+    /// (`H(shared_x || ephemeral_pubkey)`) to obtain the symmetric key used by
+    /// the AEAD path. The compressed ephemeral public key is bound into the
+    /// AEAD associated data so it cannot be swapped.
+    ///
+    /// # Returns
+    /// The serialized envelope `ephemeral_pubkey (33) || counter (8) || ciphertext || tag`.
+    pub fn encrypt_to_pubkey(recipient_pub: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let secp = Secp256k1::new();
+        let recipient = PublicKey::from_slice(recipient_pub)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid recipient public key: {}", e)))?;
+
+        let mut ephemeral_sk_bytes = [0u8; 32];
+        rand::thread_rng().fill(&mut ephemeral_sk_bytes);
+        let ephemeral_sk = SecretKey::from_slice(&ephemeral_sk_bytes)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid ephemeral key: {}", e)))?;
+        let ephemeral_pub = PublicKey::from_secret_key(&secp, &ephemeral_sk).serialize();
+
+        let key = ecies_kdf(&recipient, &ephemeral_sk, &ephemeral_pub);
+        let note = Self::encrypt(&key, plaintext, &ephemeral_pub);
+
+        let mut envelope = Vec::with_capacity(ephemeral_pub.len() + 8 + plaintext.len() + TAG_SIZE);
+        envelope.extend_from_slice(&ephemeral_pub);
+        envelope.extend_from_slice(&note.to_bytes());
+        Ok(envelope)
+    }
+
+    /// Decrypt an ECIES envelope produced by [`encrypt_to_pubkey`](Self::encrypt_to_pubkey)
+    ///
+    /// The recipient reconstructs the shared secret from its private key and the
+    /// ephemeral public key carried in the envelope.
+    pub fn decrypt_with_privkey(envelope: &[u8], recipient_priv: &[u8]) -> Result<Vec<u8>> {
+        const EPH_LEN: usize = 33;
+        if envelope.len() < EPH_LEN + 8 + TAG_SIZE {
+            return Err(VoileError::DecryptionError("ECIES envelope too short".to_string()));
+        }
+
+        let secp = Secp256k1::new();
+        let recipient_sk = SecretKey::from_slice(recipient_priv)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid recipient private key: {}", e)))?;
+
+        let mut ephemeral_pub = [0u8; EPH_LEN];
+        ephemeral_pub.copy_from_slice(&envelope[..EPH_LEN]);
+        let ephemeral = PublicKey::from_slice(&ephemeral_pub)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid ephemeral public key: {}", e)))?;
+
+        let key = ecies_kdf(&ephemeral, &recipient_sk, &ephemeral_pub);
+        let note = Self::from_bytes(&envelope[EPH_LEN..])?;
+        let _ = secp; // Secp256k1 context retained for API symmetry with encryption
+        note.decrypt(&key, &ephemeral_pub)
+    }
+
     /// Serialize the encrypted note to bytes
+    ///
+    /// Layout: `counter (8) || ciphertext (n) || tag (32)`.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(8 + self.ciphertext.len());
+        let mut bytes = Vec::with_capacity(8 + self.ciphertext.len() + TAG_SIZE);
         bytes.extend_from_slice(&self.counter.to_le_bytes());
         bytes.extend_from_slice(&self.ciphertext);
+        bytes.extend_from_slice(&self.tag);
         bytes
     }
 
     /// Deserialize an encrypted note from bytes
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 8 {
+        if bytes.len() < 8 + TAG_SIZE {
             return Err(VoileError::DecryptionError(
                 "Encrypted note too short".to_string()
             ));
         }
-        
+
         let counter = u64::from_le_bytes(
             bytes[0..8].try_into().map_err(|_| {
                 VoileError::DecryptionError("Invalid counter data".to_string())
             })?
         );
-        let ciphertext = bytes[8..].to_vec();
-        
-        Ok(Self { ciphertext, counter })
+
+        let tag_start = bytes.len() - TAG_SIZE;
+        let ciphertext = bytes[8..tag_start].to_vec();
+        let mut tag = [0u8; TAG_SIZE];
+        tag.copy_from_slice(&bytes[tag_start..]);
+
+        Ok(Self { ciphertext, counter, tag, ephemeral_pubkey: None })
+    }
+
+    /// Encrypt `plaintext` to a recipient public key, keeping the ephemeral key
+    ///
+    /// Like [`encrypt_to_pubkey`](Self::encrypt_to_pubkey) but returns a full
+    /// [`EncryptedNote`] that carries the ephemeral public key in-memory, so a
+    /// watcher holding the matching private (viewing) key can trial-decrypt it.
+    pub fn encrypt_to_recipient(recipient_pub: &[u8], plaintext: &[u8]) -> Result<Self> {
+        let secp = Secp256k1::new();
+        let recipient = PublicKey::from_slice(recipient_pub)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid recipient public key: {}", e)))?;
+
+        let mut ephemeral_sk_bytes = [0u8; 32];
+        rand::thread_rng().fill(&mut ephemeral_sk_bytes);
+        let ephemeral_sk = SecretKey::from_slice(&ephemeral_sk_bytes)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid ephemeral key: {}", e)))?;
+        let ephemeral_pub = PublicKey::from_secret_key(&secp, &ephemeral_sk).serialize();
+
+        let key = ecies_kdf(&recipient, &ephemeral_sk, &ephemeral_pub);
+        let mut note = Self::encrypt(&key, plaintext, &ephemeral_pub);
+        note.ephemeral_pubkey = Some(ephemeral_pub);
+        Ok(note)
+    }
+
+    /// The ephemeral public key, if this note was encrypted to a viewing key
+    pub fn ephemeral_pubkey(&self) -> Option<&[u8; 33]> {
+        self.ephemeral_pubkey.as_ref()
+    }
+
+    /// Attempt trial decryption with a key-agreement private key
+    ///
+    /// Returns the plaintext only if this note carries an ephemeral key and the
+    /// authentication tag matches, so a non-recipient fails cheaply with `None`.
+    pub fn try_decrypt_with_secret(&self, recipient_priv: &[u8]) -> Option<Vec<u8>> {
+        let ephemeral_pub = self.ephemeral_pubkey?;
+        let recipient_sk = SecretKey::from_slice(recipient_priv).ok()?;
+        let ephemeral = PublicKey::from_slice(&ephemeral_pub).ok()?;
+        let key = ecies_kdf(&ephemeral, &recipient_sk, &ephemeral_pub);
+        self.decrypt(&key, &ephemeral_pub).ok()
     }
 }
 
@@ -181,10 +625,10 @@ mod tests {
     fn test_encryption_roundtrip() {
         let key = EncryptionKey::generate();
         let plaintext = b"unstake_amount:1000,timing:immediate,terms:standard";
-        
-        let encrypted = EncryptedNote::encrypt(&key, plaintext);
-        let decrypted = encrypted.decrypt(&key).unwrap();
-        
+
+        let encrypted = EncryptedNote::encrypt(&key, plaintext, &[]);
+        let decrypted = encrypted.decrypt(&key, &[]).unwrap();
+
         assert_eq!(plaintext.as_slice(), decrypted.as_slice());
     }
 
@@ -193,10 +637,10 @@ mod tests {
         let key1 = EncryptionKey::generate();
         let key2 = EncryptionKey::generate();
         let plaintext = b"private_exit_data";
-        
-        let enc1 = EncryptedNote::encrypt(&key1, plaintext);
-        let enc2 = EncryptedNote::encrypt(&key2, plaintext);
-        
+
+        let enc1 = EncryptedNote::encrypt(&key1, plaintext, &[]);
+        let enc2 = EncryptedNote::encrypt(&key2, plaintext, &[]);
+
         // Ciphertexts should be different (with overwhelming probability)
         assert_ne!(enc1.ciphertext(), enc2.ciphertext());
     }
@@ -206,28 +650,110 @@ mod tests {
         let key1 = EncryptionKey::generate();
         let key2 = EncryptionKey::generate();
         let plaintext = b"secret_unstake_request";
-        
-        let encrypted = EncryptedNote::encrypt(&key1, plaintext);
-        let decrypted = encrypted.decrypt(&key2).unwrap();
-        
-        // Decryption with wrong key produces garbage
-        assert_ne!(plaintext.as_slice(), decrypted.as_slice());
+
+        let encrypted = EncryptedNote::encrypt(&key1, plaintext, &[]);
+        let result = encrypted.decrypt(&key2, &[]);
+
+        // A wrong key is now detected by the authentication tag
+        assert!(matches!(result, Err(VoileError::DecryptionError(_))));
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let key = EncryptionKey::generate();
+        let plaintext = b"secret_unstake_request";
+
+        let encrypted = EncryptedNote::encrypt(&key, plaintext, &[]);
+        let mut bytes = encrypted.to_bytes();
+        bytes[8] ^= 0xFF; // flip a bit in the first ciphertext byte
+
+        let tampered = EncryptedNote::from_bytes(&bytes).unwrap();
+        assert!(matches!(tampered.decrypt(&key, &[]), Err(VoileError::DecryptionError(_))));
+    }
+
+    #[test]
+    fn test_associated_data_must_match() {
+        let key = EncryptionKey::generate();
+        let plaintext = b"bound_to_commitment";
+
+        let encrypted = EncryptedNote::encrypt(&key, plaintext, b"commitment_a");
+
+        // Correct associated data succeeds...
+        assert!(encrypted.decrypt(&key, b"commitment_a").is_ok());
+        // ...but a different commitment is rejected.
+        assert!(matches!(
+            encrypted.decrypt(&key, b"commitment_b"),
+            Err(VoileError::DecryptionError(_))
+        ));
     }
 
     #[test]
     fn test_encrypted_note_serialization() {
         let key = EncryptionKey::generate();
         let plaintext = b"exit_note_with_terms";
-        
-        let encrypted = EncryptedNote::encrypt(&key, plaintext);
+
+        let encrypted = EncryptedNote::encrypt(&key, plaintext, &[]);
         let bytes = encrypted.to_bytes();
-        
+
         let recovered = EncryptedNote::from_bytes(&bytes).unwrap();
-        let decrypted = recovered.decrypt(&key).unwrap();
-        
+        let decrypted = recovered.decrypt(&key, &[]).unwrap();
+
         assert_eq!(plaintext.as_slice(), decrypted.as_slice());
     }
 
+    #[test]
+    fn test_padded_memo_hides_length() {
+        let key = EncryptionKey::generate();
+        let short = EncryptedNote::encrypt_padded(&key, b"hi", &[], DEFAULT_MEMO_SIZE).unwrap();
+        let long = EncryptedNote::encrypt_padded(&key, &[9u8; 400], &[], DEFAULT_MEMO_SIZE).unwrap();
+
+        // Both ciphertexts are the same length regardless of plaintext size.
+        assert_eq!(short.ciphertext().len(), DEFAULT_MEMO_SIZE);
+        assert_eq!(long.ciphertext().len(), DEFAULT_MEMO_SIZE);
+
+        assert_eq!(short.decrypt_padded(&key, &[]).unwrap(), b"hi");
+        assert_eq!(long.decrypt_padded(&key, &[]).unwrap(), vec![9u8; 400]);
+    }
+
+    #[test]
+    fn test_padded_memo_rejects_oversized_plaintext() {
+        let key = EncryptionKey::generate();
+        let result = EncryptedNote::encrypt_padded(&key, &[0u8; DEFAULT_MEMO_SIZE], &[], DEFAULT_MEMO_SIZE);
+        assert!(matches!(result, Err(VoileError::EncryptionError(_))));
+    }
+
+    #[test]
+    fn test_ecies_roundtrip() {
+        let secp = Secp256k1::new();
+        let mut sk_bytes = [7u8; 32];
+        sk_bytes[0] = 1;
+        let sk = SecretKey::from_slice(&sk_bytes).unwrap();
+        let pk = PublicKey::from_secret_key(&secp, &sk).serialize();
+
+        let plaintext = b"exit note addressed to an LP public key";
+        let envelope = EncryptedNote::encrypt_to_pubkey(&pk, plaintext).unwrap();
+        let recovered = EncryptedNote::decrypt_with_privkey(&envelope, &sk_bytes).unwrap();
+
+        assert_eq!(plaintext.as_slice(), recovered.as_slice());
+    }
+
+    #[test]
+    fn test_ecies_wrong_recipient_fails() {
+        let secp = Secp256k1::new();
+        let sk1 = [3u8; 32];
+        let sk2 = [4u8; 32];
+        let pk1 = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&sk1).unwrap()).serialize();
+
+        let envelope = EncryptedNote::encrypt_to_pubkey(&pk1, b"secret").unwrap();
+        assert!(EncryptedNote::decrypt_with_privkey(&envelope, &sk2).is_err());
+    }
+
+    #[test]
+    fn test_ecies_rejects_malformed_pubkey() {
+        let result = EncryptedNote::encrypt_to_pubkey(&[0u8; 33], b"x");
+        assert!(matches!(result, Err(VoileError::InvalidKey(_))));
+    }
+
     #[test]
     fn test_key_from_bytes() {
         let original = EncryptionKey::generate();
@@ -238,6 +764,39 @@ mod tests {
         assert_eq!(original.as_bytes(), recovered.as_bytes());
     }
 
+    #[test]
+    fn test_password_derivation_reproducible() {
+        let salt = b"voile_salt_0001";
+        let (key, key_file) = EncryptionKey::from_password(b"correct horse", salt).unwrap();
+
+        // Re-deriving from the stored key file yields the same key.
+        let reproduced = key_file.derive(b"correct horse").unwrap();
+        assert_eq!(key.as_bytes(), reproduced.as_bytes());
+
+        // A different password yields a different key.
+        let other = key_file.derive(b"battery staple").unwrap();
+        assert_ne!(key.as_bytes(), other.as_bytes());
+    }
+
+    #[test]
+    fn test_pbkdf2_derivation_roundtrip() {
+        let salt = b"pbkdf2_salt";
+        let (key, key_file) = EncryptionKey::from_password_pbkdf2(b"passphrase", salt).unwrap();
+
+        let recovered = KeyFile::from_bytes(&key_file.to_bytes()).unwrap();
+        assert_eq!(recovered, key_file);
+        assert_eq!(key.as_bytes(), recovered.derive(b"passphrase").unwrap().as_bytes());
+    }
+
+    #[test]
+    fn test_scrypt_params_out_of_range() {
+        let key_file = KeyFile {
+            salt: b"s".to_vec(),
+            algorithm: KdfAlgorithm::Scrypt { log_n: 200, r: 8, p: 1 },
+        };
+        assert!(matches!(key_file.derive(b"pw"), Err(VoileError::InvalidKey(_))));
+    }
+
     #[test]
     fn test_invalid_key_length() {
         let result = EncryptionKey::from_bytes(&[0u8; 16]);
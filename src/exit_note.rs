@@ -6,9 +6,12 @@
 
 use crate::{
     Commitment, EncryptedNote, EncryptionKey,
+    ExtractedNoteCommitment, NoteCommitmentRandomness,
     Result, VoileError,
 };
+use crate::nullifier::{Nullifier, NullifierKey, Rho};
 use rand::Rng;
+use zeroize::{Zeroize, Zeroizing};
 
 /// Represents the terms of an exit request
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -90,6 +93,119 @@ impl ExitTerms {
     }
 }
 
+/// Fixed size of a memo, in bytes
+pub const MEMO_SIZE: usize = 512;
+
+/// A fixed-length, null-padded memo buffer
+///
+/// `MemoBytes` only enforces the length and padding invariants and guarantees
+/// round-trip safety; it does not interpret the contents. Use [`Memo`] for a
+/// typed view of what the bytes mean.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MemoBytes {
+    bytes: [u8; MEMO_SIZE],
+}
+
+impl MemoBytes {
+    /// An all-zero (empty) memo
+    pub fn empty() -> Self {
+        Self { bytes: [0u8; MEMO_SIZE] }
+    }
+
+    /// Build a memo from a byte slice, null-padding up to [`MEMO_SIZE`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > MEMO_SIZE {
+            return Err(VoileError::InvalidExitNote(
+                format!("Memo of {} bytes exceeds {}", bytes.len(), MEMO_SIZE)
+            ));
+        }
+        let mut buf = [0u8; MEMO_SIZE];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self { bytes: buf })
+    }
+
+    /// Get the raw memo bytes
+    pub fn as_bytes(&self) -> &[u8; MEMO_SIZE] {
+        &self.bytes
+    }
+
+    /// Get a mutable view of the raw memo bytes (used for secure erasure)
+    pub(crate) fn as_mut_bytes(&mut self) -> &mut [u8; MEMO_SIZE] {
+        &mut self.bytes
+    }
+}
+
+impl std::fmt::Debug for MemoBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MemoBytes({} bytes)", MEMO_SIZE)
+    }
+}
+
+/// A typed interpretation of a [`MemoBytes`] buffer
+///
+/// The leading byte of the buffer governs parsing. Unknown non-text type codes
+/// are surfaced as [`Memo::Arbitrary`] rather than erroring, so that memos
+/// written by newer versions still round-trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Memo {
+    /// An all-zero memo carrying no data
+    Empty,
+    /// A UTF-8 text memo (type code 1)
+    Text(String),
+    /// Opaque bytes (type code 2, or any unknown non-text code)
+    Arbitrary(Vec<u8>),
+}
+
+impl Memo {
+    /// Type code marking a UTF-8 text memo
+    const TYPE_TEXT: u8 = 1;
+    /// Type code marking an opaque byte memo
+    const TYPE_ARBITRARY: u8 = 2;
+
+    /// Interpret a raw memo buffer
+    pub fn from_memo_bytes(memo: &MemoBytes) -> Self {
+        let bytes = memo.as_bytes();
+        if bytes.iter().all(|b| *b == 0) {
+            return Memo::Empty;
+        }
+
+        let payload = strip_trailing_zeros(&bytes[1..]);
+        match bytes[0] {
+            Self::TYPE_TEXT => match std::str::from_utf8(payload) {
+                Ok(text) => Memo::Text(text.to_string()),
+                Err(_) => Memo::Arbitrary(payload.to_vec()),
+            },
+            // Type code 2 and any reserved/unknown code fall back to opaque bytes.
+            _ => Memo::Arbitrary(payload.to_vec()),
+        }
+    }
+
+    /// Encode this memo into a fixed-length buffer
+    pub fn to_memo_bytes(&self) -> Result<MemoBytes> {
+        match self {
+            Memo::Empty => Ok(MemoBytes::empty()),
+            Memo::Text(text) => {
+                let mut buf = Vec::with_capacity(1 + text.len());
+                buf.push(Self::TYPE_TEXT);
+                buf.extend_from_slice(text.as_bytes());
+                MemoBytes::from_bytes(&buf)
+            }
+            Memo::Arbitrary(data) => {
+                let mut buf = Vec::with_capacity(1 + data.len());
+                buf.push(Self::TYPE_ARBITRARY);
+                buf.extend_from_slice(data);
+                MemoBytes::from_bytes(&buf)
+            }
+        }
+    }
+}
+
+/// Drop trailing zero padding from a memo payload
+fn strip_trailing_zeros(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    &bytes[..end]
+}
+
 /// A private exit note containing unstake details
 ///
 /// This note is created locally on the user's device and contains all the
@@ -109,6 +225,10 @@ pub struct ExitNote {
     created_at: u64,
     /// Blinding factor for commitment
     blinding_factor: [u8; 32],
+    /// Per-note `rho` seed used to derive the note's nullifier
+    rho: Rho,
+    /// Fixed-length encrypted off-chain memo (payout address, routing hints)
+    memo: MemoBytes,
 }
 
 impl ExitNote {
@@ -135,6 +255,8 @@ impl ExitNote {
             .map(|d| d.as_secs())
             .unwrap_or(0);
         
+        let rho = Rho::from_note_id(&note_id);
+
         Self {
             note_id,
             amount,
@@ -142,6 +264,8 @@ impl ExitNote {
             terms,
             created_at,
             blinding_factor,
+            rho,
+            memo: MemoBytes::empty(),
         }
     }
 
@@ -170,30 +294,65 @@ impl ExitNote {
         self.created_at
     }
 
+    /// Get the note's `rho` seed
+    pub fn rho(&self) -> &Rho {
+        &self.rho
+    }
+
+    /// Get the typed interpretation of this note's memo
+    pub fn memo(&self) -> Memo {
+        Memo::from_memo_bytes(&self.memo)
+    }
+
+    /// Get the raw memo buffer
+    pub fn memo_bytes(&self) -> &MemoBytes {
+        &self.memo
+    }
+
+    /// Set the note's memo from a typed value
+    ///
+    /// The memo stays encrypted alongside the note and never touches the
+    /// commitment's public inputs.
+    pub fn set_memo(&mut self, memo: &Memo) -> Result<()> {
+        self.memo = memo.to_memo_bytes()?;
+        Ok(())
+    }
+
+    /// Compute the note's nullifier under the given nullifier key
+    ///
+    /// The nullifier is deterministic for a given `(note, nk)` pair, so the
+    /// contract layer can maintain a set of seen nullifiers and reject a note
+    /// revealed more than once.
+    pub fn nullifier(&self, nk: &NullifierKey) -> Nullifier {
+        Nullifier::derive(nk, &self.rho, &self.blinding_factor)
+    }
+
     /// Serialize the exit note to bytes
     pub fn to_bytes(&self) -> Vec<u8> {
         let terms_bytes = self.terms.to_bytes();
-        let mut bytes = Vec::with_capacity(32 + 8 + 32 + 8 + 32 + terms_bytes.len() + 2);
-        
+        let mut bytes = Vec::with_capacity(32 + 8 + 32 + 8 + 32 + 32 + terms_bytes.len() + 2);
+
         bytes.extend_from_slice(&self.note_id);
         bytes.extend_from_slice(&self.amount.to_le_bytes());
         bytes.extend_from_slice(&self.owner);
         bytes.extend_from_slice(&self.created_at.to_le_bytes());
         bytes.extend_from_slice(&self.blinding_factor);
+        bytes.extend_from_slice(self.rho.as_bytes());
         bytes.extend_from_slice(&(terms_bytes.len() as u16).to_le_bytes());
         bytes.extend_from_slice(&terms_bytes);
-        
+        bytes.extend_from_slice(self.memo.as_bytes());
+
         bytes
     }
 
     /// Deserialize an exit note from bytes
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 114 {
+        if bytes.len() < 146 {
             return Err(VoileError::InvalidExitNote(
                 format!("Exit note too short: {} bytes", bytes.len())
             ));
         }
-        
+
         let mut note_id = [0u8; 32];
         note_id.copy_from_slice(&bytes[0..32]);
         
@@ -214,21 +373,28 @@ impl ExitNote {
         
         let mut blinding_factor = [0u8; 32];
         blinding_factor.copy_from_slice(&bytes[80..112]);
-        
+
+        let mut rho_bytes = [0u8; 32];
+        rho_bytes.copy_from_slice(&bytes[112..144]);
+        let rho = Rho::from_bytes(&rho_bytes);
+
         let terms_len = u16::from_le_bytes(
-            bytes[112..114].try_into().map_err(|_| {
+            bytes[144..146].try_into().map_err(|_| {
                 VoileError::InvalidExitNote("Invalid terms length data".to_string())
             })?
         ) as usize;
-        
-        if bytes.len() < 114 + terms_len {
+
+        if bytes.len() < 146 + terms_len + MEMO_SIZE {
             return Err(VoileError::InvalidExitNote(
                 "Exit note truncated".to_string()
             ));
         }
-        
-        let terms = ExitTerms::from_bytes(&bytes[114..114 + terms_len])?;
-        
+
+        let terms = ExitTerms::from_bytes(&bytes[146..146 + terms_len])?;
+
+        let memo_start = 146 + terms_len;
+        let memo = MemoBytes::from_bytes(&bytes[memo_start..memo_start + MEMO_SIZE])?;
+
         Ok(Self {
             note_id,
             amount,
@@ -236,9 +402,19 @@ impl ExitNote {
             terms,
             created_at,
             blinding_factor,
+            rho,
+            memo,
         })
     }
 
+    /// Serialize the note into a buffer that is guaranteed to be wiped on drop
+    ///
+    /// Callers that handle the decrypted note can opt into erasure of the
+    /// intermediate plaintext without managing the wipe themselves.
+    pub fn to_bytes_zeroizing(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.to_bytes())
+    }
+
     /// Compute the commitment for this exit note
     ///
     /// This commitment can be safely published on-chain without revealing
@@ -248,6 +424,24 @@ impl ExitNote {
         Commitment::new(&note_bytes, &self.blinding_factor)
     }
 
+    /// Compute the algebraic (Pedersen) commitment for this exit note
+    ///
+    /// Unlike [`commitment`](Self::commitment), this commitment is additively
+    /// homomorphic in `amount` and can be opened inside an arithmetic circuit,
+    /// so amounts can later be proven and balanced in a SNARK. The hash
+    /// [`commitment`](Self::commitment) remains available for non-circuit
+    /// callers that only need an opaque blob.
+    pub fn extracted_commitment(&self) -> ExtractedNoteCommitment {
+        let blinding = NoteCommitmentRandomness::from_bytes(&self.blinding_factor);
+        ExtractedNoteCommitment::compute(
+            self.amount,
+            &self.owner,
+            &self.terms.to_bytes(),
+            self.created_at,
+            &blinding,
+        )
+    }
+
     /// Encrypt the exit note for private storage
     ///
     /// # Arguments
@@ -256,8 +450,10 @@ impl ExitNote {
     /// # Returns
     /// An encrypted version of this note
     pub fn encrypt(&self, key: &EncryptionKey) -> EncryptedNote {
-        let plaintext = self.to_bytes();
-        EncryptedNote::encrypt(key, &plaintext)
+        // Wrap the serialized plaintext so the secret-bearing buffer is wiped
+        // once the ciphertext has been produced.
+        let plaintext = Zeroizing::new(self.to_bytes());
+        EncryptedNote::encrypt(key, &plaintext, &[])
     }
 
     /// Decrypt an exit note
@@ -269,7 +465,7 @@ impl ExitNote {
     /// # Returns
     /// Result containing the decrypted ExitNote or an error
     pub fn decrypt(encrypted: &EncryptedNote, key: &EncryptionKey) -> Result<Self> {
-        let plaintext = encrypted.decrypt(key)?;
+        let plaintext = encrypted.decrypt(key, &[])?;
         Self::from_bytes(&plaintext)
     }
 
@@ -280,6 +476,15 @@ impl ExitNote {
     }
 }
 
+impl Drop for ExitNote {
+    /// Wipe secret-bearing fields so they do not linger in freed memory
+    fn drop(&mut self) {
+        self.note_id.zeroize();
+        self.blinding_factor.zeroize();
+        self.memo.as_mut_bytes().zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,13 +554,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memo_roundtrip_through_encryption() {
+        let mut note = ExitNote::new(2500, [7u8; 32], ExitTerms::Standard);
+        note.set_memo(&Memo::Text("payout:0xabc routing:fast".to_string())).unwrap();
+
+        let key = EncryptionKey::generate();
+        let encrypted = note.encrypt(&key);
+        let decrypted = ExitNote::decrypt(&encrypted, &key).unwrap();
+
+        assert_eq!(decrypted.memo(), Memo::Text("payout:0xabc routing:fast".to_string()));
+    }
+
+    #[test]
+    fn test_memo_variants() {
+        assert_eq!(ExitNote::new(1, [0u8; 32], ExitTerms::Standard).memo(), Memo::Empty);
+
+        let arbitrary = Memo::Arbitrary(vec![1, 2, 3, 4]);
+        let roundtrip = Memo::from_memo_bytes(&arbitrary.to_memo_bytes().unwrap());
+        assert_eq!(roundtrip, arbitrary);
+    }
+
+    #[test]
+    fn test_memo_does_not_affect_commitment_publicly() {
+        // The memo is part of the (private) note bytes but never revealed on-chain.
+        let mut note = ExitNote::new(1000, [1u8; 32], ExitTerms::Standard);
+        let before = note.to_bytes().len();
+        note.set_memo(&Memo::Text("hi".to_string())).unwrap();
+        assert_eq!(note.to_bytes().len(), before); // fixed-size memo keeps length constant
+    }
+
     #[test]
     fn test_different_notes_different_commitments() {
         let owner = [1u8; 32];
         let note1 = ExitNote::new(1000, owner, ExitTerms::Standard);
         let note2 = ExitNote::new(1000, owner, ExitTerms::Standard);
-        
+
         // Different notes (different IDs/blinding) should have different commitments
         assert_ne!(note1.commitment(), note2.commitment());
     }
+
+    use proptest::prelude::*;
+
+    impl Arbitrary for ExitTerms {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            prop_oneof![
+                Just(ExitTerms::Immediate),
+                Just(ExitTerms::Standard),
+                any::<u64>().prop_map(|blocks| ExitTerms::Delayed { blocks }),
+                (any::<u16>(), any::<u16>()).prop_map(|(min_rate_bps, max_slippage_bps)| {
+                    ExitTerms::Custom { min_rate_bps, max_slippage_bps }
+                }),
+            ]
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for ExitNote {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            (
+                any::<[u8; 32]>(),
+                any::<u64>(),
+                any::<[u8; 32]>(),
+                any::<u64>(),
+                any::<[u8; 32]>(),
+                any::<ExitTerms>(),
+            )
+                .prop_map(|(note_id, amount, owner, created_at, blinding_factor, terms)| {
+                    let rho = Rho::from_note_id(&note_id);
+                    ExitNote {
+                        note_id,
+                        amount,
+                        owner,
+                        terms,
+                        created_at,
+                        blinding_factor,
+                        rho,
+                        memo: MemoBytes::empty(),
+                    }
+                })
+                .boxed()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_exit_terms_roundtrip(terms in any::<ExitTerms>()) {
+            prop_assert_eq!(ExitTerms::from_bytes(&terms.to_bytes()).unwrap(), terms);
+        }
+
+        #[test]
+        fn prop_exit_note_serialization_roundtrip(note in any::<ExitNote>()) {
+            let bytes = note.to_bytes();
+            let recovered = ExitNote::from_bytes(&bytes).unwrap();
+            // Re-serializing the recovered note reproduces the exact bytes.
+            prop_assert_eq!(recovered.to_bytes(), bytes);
+        }
+
+        #[test]
+        fn prop_encryption_roundtrip(note in any::<ExitNote>()) {
+            let key = EncryptionKey::generate();
+            let encrypted = note.encrypt(&key);
+            let decrypted = ExitNote::decrypt(&encrypted, &key).unwrap();
+            prop_assert_eq!(decrypted.to_bytes(), note.to_bytes());
+        }
+
+        #[test]
+        fn prop_from_bytes_never_panics(data in proptest::collection::vec(any::<u8>(), 0..300)) {
+            // Arbitrary/truncated input must never panic; it either parses or
+            // returns a VoileError.
+            let _ = ExitNote::from_bytes(&data);
+        }
+    }
 }
@@ -11,16 +11,28 @@
 //! Inside Voile, the user's device creates a private exit note, which contains their pending
 //! unstake and the terms they want. This note is encrypted and only its commitment appears on-chain.
 
+pub mod bundle;
 pub mod commitment;
+pub mod credential;
 pub mod encryption;
 pub mod exit_note;
+pub mod nullifier;
 pub mod proof;
+pub mod range;
+pub mod smt;
+pub mod viewing;
 pub mod error;
 
-pub use commitment::Commitment;
+pub use commitment::{Commitment, ExtractedNoteCommitment, NoteCommitmentRandomness, PedersenCommitment};
+pub use credential::{Credential, CredentialProof, CredentialRequest, Issuer, IssuerPublicKey};
 pub use encryption::{EncryptedNote, EncryptionKey};
-pub use exit_note::ExitNote;
-pub use proof::{ExitProof, ProofGenerator, ProofVerifier};
+pub use exit_note::{ExitNote, Memo, MemoBytes};
+pub use nullifier::{Nullifier, NullifierKey, Rho};
+pub use proof::{DisclosureProof, ExitProof, ProofGenerator, ProofVerifier, RevealedAttribute};
+pub use range::RangeProof;
+pub use smt::{MerkleProof, SparseMerkleTree};
+pub use viewing::{IncomingViewingKey, Scope};
+pub use bundle::{Bundle, BundleNote, BundleProof};
 pub use error::VoileError;
 
 /// Result type for Voile Protocol operations
@@ -0,0 +1,168 @@
+//! Nullifier subsystem for Voile Protocol
+//!
+//! A nullifier lets each exit note be revealed and invalidated exactly once.
+//! Following Orchard's design, the nullifier is derived deterministically from
+//! a per-note [`Rho`] value, a per-owner [`NullifierKey`], and the note's
+//! blinding factor, so revealing the same note twice always produces the
+//! identical [`Nullifier`] and can be rejected by the contract layer.
+
+use curve25519_dalek::scalar::Scalar;
+use sha3::{Digest, Keccak256};
+use zeroize::Zeroize;
+use crate::{Result, VoileError};
+
+/// A per-owner key used to derive nullifiers
+///
+/// Knowledge of the nullifier key is required to compute a note's nullifier,
+/// so only the owner (or a delegate given the key) can invalidate the note.
+#[derive(Clone)]
+pub struct NullifierKey {
+    key: [u8; 32],
+}
+
+impl NullifierKey {
+    /// Create a nullifier key from raw bytes
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self { key: *bytes }
+    }
+
+    /// Get the key bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
+
+impl Drop for NullifierKey {
+    /// Wipe the key material so it does not linger in freed memory
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+/// The `rho` value seeded into each note at creation
+///
+/// Derived from the note id for a freshly created note, or from the nullifier
+/// of a previously spent note when building a spend chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rho {
+    bytes: [u8; 32],
+}
+
+impl Rho {
+    /// Seed `rho` from a note id
+    pub fn from_note_id(note_id: &[u8; 32]) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_rho");
+        hasher.update(note_id);
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hasher.finalize());
+        Self { bytes }
+    }
+
+    /// Seed `rho` from the nullifier of a previously spent note (spend chains)
+    pub fn from_nullifier(nullifier: &Nullifier) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_rho");
+        hasher.update(nullifier.as_bytes());
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hasher.finalize());
+        Self { bytes }
+    }
+
+    /// Get the raw `rho` bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// Build a `rho` from raw bytes
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self { bytes: *bytes }
+    }
+}
+
+/// A nullifier that invalidates an exit note on reveal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nullifier {
+    bytes: [u8; 32],
+}
+
+impl Nullifier {
+    /// Compute a nullifier from its inputs
+    ///
+    /// `nf = PRF_nk(rho) + psi mod p`, collapsed to 32 bytes, where `psi` is a
+    /// domain-separated hash of `rho || blinding_factor`. The modular addition
+    /// is performed over the prime-order scalar field.
+    pub(crate) fn derive(nk: &NullifierKey, rho: &Rho, blinding_factor: &[u8; 32]) -> Self {
+        let mut prf = Keccak256::new();
+        prf.update(b"voile_prf_nf");
+        prf.update(nk.as_bytes());
+        prf.update(rho.as_bytes());
+        let mut prf_bytes = [0u8; 32];
+        prf_bytes.copy_from_slice(&prf.finalize());
+
+        let mut psi = Keccak256::new();
+        psi.update(b"voile_psi");
+        psi.update(rho.as_bytes());
+        psi.update(blinding_factor);
+        let mut psi_bytes = [0u8; 32];
+        psi_bytes.copy_from_slice(&psi.finalize());
+
+        let nf = Scalar::from_bytes_mod_order(prf_bytes) + Scalar::from_bytes_mod_order(psi_bytes);
+        Self { bytes: nf.to_bytes() }
+    }
+
+    /// Get the nullifier bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// Serialize the nullifier to bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Deserialize a nullifier from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 32 {
+            return Err(VoileError::InvalidExitNote(
+                format!("Expected 32 bytes, got {}", bytes.len())
+            ));
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(bytes);
+        Ok(Self { bytes: buf })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exit_note::{ExitNote, ExitTerms};
+
+    #[test]
+    fn test_nullifier_is_deterministic() {
+        let note = ExitNote::new(1000, [1u8; 32], ExitTerms::Standard);
+        let nk = NullifierKey::from_bytes(&[9u8; 32]);
+
+        assert_eq!(note.nullifier(&nk), note.nullifier(&nk));
+    }
+
+    #[test]
+    fn test_nullifier_depends_on_key() {
+        let note = ExitNote::new(1000, [1u8; 32], ExitTerms::Standard);
+        let nk1 = NullifierKey::from_bytes(&[1u8; 32]);
+        let nk2 = NullifierKey::from_bytes(&[2u8; 32]);
+
+        assert_ne!(note.nullifier(&nk1), note.nullifier(&nk2));
+    }
+
+    #[test]
+    fn test_nullifier_serialization() {
+        let note = ExitNote::new(1000, [1u8; 32], ExitTerms::Standard);
+        let nk = NullifierKey::from_bytes(&[9u8; 32]);
+        let nf = note.nullifier(&nk);
+
+        let recovered = Nullifier::from_bytes(&nf.to_bytes()).unwrap();
+        assert_eq!(nf, recovered);
+    }
+}
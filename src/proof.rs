@@ -46,6 +46,34 @@ pub struct ExitProof {
     verification_tag: [u8; 32],
     /// Public input: nullifier to prevent double-spending
     nullifier: [u8; 32],
+    /// Optional encrypted memo carried alongside the proof
+    ///
+    /// When present, its commitment is folded into the Fiat-Shamir challenge
+    /// so the memo cannot be stripped or swapped after the proof is formed.
+    memo_ciphertext: Option<Vec<u8>>,
+}
+
+/// Serialization version byte for proofs that carry an encrypted memo
+const PROOF_VERSION_MEMO: u8 = 1;
+
+/// Commit to an (optional) memo ciphertext under a domain
+///
+/// Absent memos commit to the zero value so a plain proof's challenge is
+/// computed the same way whether or not the memo feature is used.
+fn memo_commitment(domain: &[u8; 32], memo_ciphertext: &Option<Vec<u8>>) -> [u8; 32] {
+    match memo_ciphertext {
+        None => [0u8; 32],
+        Some(ct) => {
+            let mut hasher = Keccak256::new();
+            hasher.update(b"voile_memo_commitment");
+            hasher.update(domain);
+            hasher.update((ct.len() as u32).to_le_bytes());
+            hasher.update(ct);
+            let mut out = [0u8; 32];
+            out.copy_from_slice(&hasher.finalize());
+            out
+        }
+    }
 }
 
 impl ExitProof {
@@ -69,45 +97,96 @@ impl ExitProof {
         &self.verification_tag
     }
 
+    /// Get the encrypted memo ciphertext, if the proof carries one
+    pub fn memo_ciphertext(&self) -> Option<&[u8]> {
+        self.memo_ciphertext.as_deref()
+    }
+
     /// Serialize the proof to bytes
+    ///
+    /// A proof without a memo keeps the legacy 160-byte layout. A proof with a
+    /// memo is prefixed with a version byte and appends the ciphertext, so a
+    /// light client can still parse old proofs by length.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(160);
-        bytes.extend_from_slice(self.commitment.as_bytes());
-        bytes.extend_from_slice(&self.announcement);
-        bytes.extend_from_slice(&self.response);
-        bytes.extend_from_slice(&self.verification_tag);
-        bytes.extend_from_slice(&self.nullifier);
-        bytes
+        match &self.memo_ciphertext {
+            None => {
+                let mut bytes = Vec::with_capacity(160);
+                bytes.extend_from_slice(self.commitment.as_bytes());
+                bytes.extend_from_slice(&self.announcement);
+                bytes.extend_from_slice(&self.response);
+                bytes.extend_from_slice(&self.verification_tag);
+                bytes.extend_from_slice(&self.nullifier);
+                bytes
+            }
+            Some(ct) => {
+                let mut bytes = Vec::with_capacity(165 + ct.len());
+                bytes.push(PROOF_VERSION_MEMO);
+                bytes.extend_from_slice(self.commitment.as_bytes());
+                bytes.extend_from_slice(&self.announcement);
+                bytes.extend_from_slice(&self.response);
+                bytes.extend_from_slice(&self.verification_tag);
+                bytes.extend_from_slice(&self.nullifier);
+                bytes.extend_from_slice(&(ct.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(ct);
+                bytes
+            }
+        }
     }
 
     /// Deserialize a proof from bytes
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() != 160 {
+        // Legacy, memo-free proofs are exactly 160 bytes with no version byte.
+        if bytes.len() == 160 {
+            return Self::parse_core(bytes, None);
+        }
+
+        // Versioned layout: a leading version byte, the 160-byte core, then a
+        // length-prefixed memo ciphertext.
+        if bytes.is_empty() || bytes[0] != PROOF_VERSION_MEMO {
             return Err(VoileError::ProofVerificationFailed(
-                format!("Invalid proof size: expected 160, got {}", bytes.len())
+                format!("Invalid proof size or version: got {} bytes", bytes.len())
             ));
         }
-        
+        if bytes.len() < 165 {
+            return Err(VoileError::ProofVerificationFailed(
+                "Truncated memo proof".to_string()
+            ));
+        }
+        let mut len_bytes = [0u8; 4];
+        len_bytes.copy_from_slice(&bytes[161..165]);
+        let memo_len = u32::from_le_bytes(len_bytes) as usize;
+        if bytes.len() != 165 + memo_len {
+            return Err(VoileError::ProofVerificationFailed(
+                format!("Invalid memo proof size: expected {}, got {}", 165 + memo_len, bytes.len())
+            ));
+        }
+        let memo = bytes[165..].to_vec();
+        Self::parse_core(&bytes[1..161], Some(memo))
+    }
+
+    /// Parse the fixed 160-byte core, attaching an optional memo
+    fn parse_core(bytes: &[u8], memo_ciphertext: Option<Vec<u8>>) -> Result<Self> {
         let commitment = Commitment::from_bytes(&bytes[0..32])?;
-        
+
         let mut announcement = [0u8; 32];
         announcement.copy_from_slice(&bytes[32..64]);
-        
+
         let mut response = [0u8; 32];
         response.copy_from_slice(&bytes[64..96]);
-        
+
         let mut verification_tag = [0u8; 32];
         verification_tag.copy_from_slice(&bytes[96..128]);
-        
+
         let mut nullifier = [0u8; 32];
         nullifier.copy_from_slice(&bytes[128..160]);
-        
+
         Ok(Self {
             commitment,
             announcement,
             response,
             verification_tag,
             nullifier,
+            memo_ciphertext,
         })
     }
 
@@ -117,6 +196,78 @@ impl ExitProof {
     }
 }
 
+/// A single attribute revealed alongside a [`DisclosureProof`]
+///
+/// Carries the attribute index, its cleartext value, and the per-attribute
+/// opening randomness so the verifier can confirm the value against the proof.
+#[derive(Clone, Debug)]
+pub struct RevealedAttribute {
+    /// Position of this attribute in the note's attribute vector
+    pub index: usize,
+    /// The revealed cleartext value
+    pub value: Vec<u8>,
+    /// The per-attribute opening randomness `k_i`
+    pub opening: [u8; 32],
+}
+
+/// A selective-disclosure proof over a note's attribute vector
+///
+/// Proves knowledge of all attributes committed in [`commitment`](Self::commitment)
+/// while revealing only a chosen subset. The hidden attributes contribute
+/// blinded responses that are bound into the aggregate verification tag but
+/// are not individually openable, so the verifier learns exactly the revealed
+/// fields and nothing else.
+#[derive(Clone, Debug)]
+pub struct DisclosureProof {
+    /// The commitment to the exit note (identical regardless of disclosure)
+    commitment: Commitment,
+    /// Aggregate announcement over every per-attribute announcement
+    aggregate_announcement: [u8; 32],
+    /// Per-attribute announcements `A_i`
+    announcements: Vec<[u8; 32]>,
+    /// Per-attribute blinded responses `s_i`
+    responses: Vec<[u8; 32]>,
+    /// Aggregate verification tag binding all responses to the challenge
+    verification_tag: [u8; 32],
+}
+
+impl DisclosureProof {
+    /// Get the commitment
+    pub fn commitment(&self) -> &Commitment {
+        &self.commitment
+    }
+
+    /// Number of attributes in the committed vector
+    pub fn attribute_count(&self) -> usize {
+        self.announcements.len()
+    }
+}
+
+/// Canonical attribute vector for an exit note
+///
+/// The ordering is fixed so prover and verifier agree on attribute indices.
+fn note_attributes(note: &ExitNote) -> Vec<Vec<u8>> {
+    vec![
+        note.amount().to_le_bytes().to_vec(),
+        note.owner().to_vec(),
+        note.terms().to_bytes(),
+        note.created_at().to_le_bytes().to_vec(),
+    ]
+}
+
+/// Serialize a set of revealed indices for binding into the challenge
+fn encode_indices(indices: &[usize]) -> Vec<u8> {
+    let mut sorted: Vec<usize> = indices.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut bytes = Vec::with_capacity(2 + sorted.len() * 4);
+    bytes.extend_from_slice(&(sorted.len() as u16).to_le_bytes());
+    for i in sorted {
+        bytes.extend_from_slice(&(i as u32).to_le_bytes());
+    }
+    bytes
+}
+
 /// Generates zero-knowledge proofs for exit transactions
 ///
 /// This runs locally on the user's device and keeps all sensitive
@@ -155,34 +306,103 @@ impl ProofGenerator {
     /// # Returns
     /// Result containing the ExitProof or an error
     pub fn generate(&self, note: &ExitNote, owner_secret: &[u8; 32]) -> Result<ExitProof> {
+        // The nonce-free API is a thin wrapper using a zero nonce.
+        self.generate_with_nonce(note, owner_secret, &[0u8; 32])
+    }
+
+    /// Generate a proof that is bound to a verifier-supplied nonce
+    ///
+    /// Folding the nonce into the Fiat-Shamir challenge ties the proof to a
+    /// single verification session, so a captured proof cannot be replayed
+    /// against a different nonce.
+    ///
+    /// # Arguments
+    /// * `note` - The exit note to prove
+    /// * `owner_secret` - The owner's secret key for authorization
+    /// * `nonce` - A fresh nonce issued by the verifier
+    pub fn generate_with_nonce(
+        &self,
+        note: &ExitNote,
+        owner_secret: &[u8; 32],
+        nonce: &[u8; 32],
+    ) -> Result<ExitProof> {
+        self.generate_inner(note, owner_secret, nonce, None)
+    }
+
+    /// Generate a proof carrying an encrypted memo for the recipient
+    ///
+    /// The 512-byte memo is encrypted under a shared secret derived from the
+    /// owner secret and the recipient tag, and its commitment is folded into
+    /// the challenge so it cannot be stripped or swapped. The verifier checks
+    /// the commitment but learns nothing about the plaintext.
+    ///
+    /// # Arguments
+    /// * `note` - The exit note to prove
+    /// * `owner_secret` - The owner's secret key for authorization
+    /// * `memo` - The fixed 512-byte memo to encrypt
+    /// * `recipient_tag` - A public tag identifying the recipient
+    pub fn generate_with_memo(
+        &self,
+        note: &ExitNote,
+        owner_secret: &[u8; 32],
+        memo: &[u8; 512],
+        recipient_tag: &[u8],
+    ) -> Result<ExitProof> {
+        let key = self.memo_key(owner_secret, recipient_tag)?;
+        let ciphertext = crate::encryption::EncryptedNote::encrypt(&key, memo, &[]).to_bytes();
+        self.generate_inner(note, owner_secret, &[0u8; 32], Some(ciphertext))
+    }
+
+    /// Derive the AEAD key that encrypts a memo to a recipient
+    fn memo_key(&self, owner_secret: &[u8; 32], recipient_tag: &[u8]) -> Result<crate::encryption::EncryptionKey> {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_memo_key");
+        hasher.update(self.domain);
+        hasher.update(owner_secret);
+        hasher.update(recipient_tag);
+        let derived = hasher.finalize();
+        crate::encryption::EncryptionKey::from_bytes(&derived)
+    }
+
+    /// Core proof construction shared by the nonce and memo entry points
+    fn generate_inner(
+        &self,
+        note: &ExitNote,
+        owner_secret: &[u8; 32],
+        nonce: &[u8; 32],
+        memo_ciphertext: Option<Vec<u8>>,
+    ) -> Result<ExitProof> {
         // Compute the commitment
         let commitment = note.commitment();
-        
+
         // Generate nullifier to prevent double-spending
         let nullifier = self.compute_nullifier(note.note_id(), owner_secret);
-        
+
         // Step 1: Generate random nonce k
         let mut rng = rand::thread_rng();
         let mut random_k = [0u8; 32];
         rng.fill(&mut random_k);
-        
+
         // Step 2: Compute announcement A = H(domain || k)
         let announcement = self.compute_announcement(&random_k);
-        
-        // Step 3: Compute challenge c = H(domain || commitment || nullifier || A)
+
+        // Step 3: Compute challenge c = H(domain || commitment || nullifier || A || nonce || memo)
+        let memo_comm = memo_commitment(&self.domain, &memo_ciphertext);
         let challenge = self.compute_challenge(
             &commitment,
             &nullifier,
             &announcement,
+            nonce,
+            &memo_comm,
         );
-        
+
         // Step 4: Compute response s = H(domain || k || c || secret)
         let response = self.compute_response(
             &random_k,
             &challenge,
             owner_secret,
         );
-        
+
         // Step 5: Compute verification tag v = H(domain || s || c || A || commitment || nullifier)
         let verification_tag = self.compute_verification_tag(
             &response,
@@ -191,16 +411,221 @@ impl ProofGenerator {
             &commitment,
             &nullifier,
         );
-        
+
         Ok(ExitProof {
             commitment,
             announcement,
             response,
             verification_tag,
             nullifier,
+            memo_ciphertext,
         })
     }
 
+    /// Generate an exit proof together with an anonymous-credential proof
+    ///
+    /// Proves, bound to the exit proof's transcript, that the prover holds a
+    /// valid issuer credential over the same commitment — authorizing the exit
+    /// without revealing the credential or linking it to other exits.
+    ///
+    /// # Arguments
+    /// * `note` - The exit note to prove
+    /// * `owner_secret` - The owner's secret key for authorization
+    /// * `credential` - An issuer credential over this note's commitment
+    pub fn generate_with_credential(
+        &self,
+        note: &ExitNote,
+        owner_secret: &[u8; 32],
+        credential: &crate::credential::Credential,
+    ) -> Result<(ExitProof, crate::credential::CredentialProof)> {
+        let proof = self.generate(note, owner_secret)?;
+        let cred = crate::credential::prove(
+            &self.domain,
+            credential,
+            proof.commitment.as_bytes(),
+            &proof.nullifier,
+            &proof.announcement,
+        );
+        Ok((proof, cred))
+    }
+
+    /// Generate an exit proof together with a range proof on the amount
+    ///
+    /// The range proof attests that the note's amount `v` satisfies
+    /// `0 <= v < 2^64` without revealing it, guarding against a malicious
+    /// prover encoding a negative or overflowing value. The owner secret seeds
+    /// the value-commitment blinding so the proof is reproducible.
+    ///
+    /// # Arguments
+    /// * `note` - The exit note to prove
+    /// * `owner_secret` - The owner's secret key for authorization
+    pub fn generate_with_range(
+        &self,
+        note: &ExitNote,
+        owner_secret: &[u8; 32],
+    ) -> Result<(ExitProof, crate::range::RangeProof)> {
+        let proof = self.generate(note, owner_secret)?;
+        let range = crate::range::prove(&self.domain, note.amount(), owner_secret);
+        Ok((proof, range))
+    }
+
+    /// Generate a selective-disclosure proof revealing a chosen subset
+    ///
+    /// Returns the proof together with the cleartext (and opening randomness)
+    /// of the revealed attributes. The revealed indices are bound into the
+    /// Fiat-Shamir challenge so the prover cannot later claim a different
+    /// disclosure subset.
+    ///
+    /// # Arguments
+    /// * `note` - The multi-attribute exit note
+    /// * `revealed_indices` - Which attribute indices to disclose
+    pub fn generate_disclosed(
+        &self,
+        note: &ExitNote,
+        revealed_indices: &[usize],
+    ) -> Result<(DisclosureProof, Vec<RevealedAttribute>)> {
+        let attributes = note_attributes(note);
+        let n = attributes.len();
+        for &i in revealed_indices {
+            if i >= n {
+                return Err(VoileError::ProofGenerationError(
+                    format!("Revealed index {} out of range (n={})", i, n)
+                ));
+            }
+        }
+
+        let commitment = note.commitment();
+        let mut rng = rand::thread_rng();
+
+        // Per-attribute announcements from fresh openings.
+        let mut openings = Vec::with_capacity(n);
+        let mut announcements = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut k = [0u8; 32];
+            rng.fill(&mut k);
+            announcements.push(self.compute_attr_announcement(i, &k));
+            openings.push(k);
+        }
+
+        let aggregate_announcement = self.compute_aggregate_announcement(&announcements);
+
+        let challenge = self.compute_disclosure_challenge(
+            &commitment,
+            revealed_indices,
+            &aggregate_announcement,
+        );
+
+        // Per-attribute responses.
+        let mut responses = Vec::with_capacity(n);
+        for i in 0..n {
+            responses.push(self.compute_attr_response(&openings[i], &challenge, &attributes[i]));
+        }
+
+        let verification_tag = self.compute_disclosure_tag(
+            &challenge,
+            &aggregate_announcement,
+            &responses,
+            &commitment,
+        );
+
+        let proof = DisclosureProof {
+            commitment,
+            aggregate_announcement,
+            announcements,
+            responses,
+            verification_tag,
+        };
+
+        let revealed = revealed_indices
+            .iter()
+            .map(|&i| RevealedAttribute {
+                index: i,
+                value: attributes[i].clone(),
+                opening: openings[i],
+            })
+            .collect();
+
+        Ok((proof, revealed))
+    }
+
+    /// Per-attribute announcement `A_i = H(domain || "attr_ann" || i || k_i)`
+    fn compute_attr_announcement(&self, index: usize, opening: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_ann");
+        hasher.update(self.domain);
+        hasher.update((index as u32).to_le_bytes());
+        hasher.update(opening);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Aggregate announcement over all per-attribute announcements
+    fn compute_aggregate_announcement(&self, announcements: &[[u8; 32]]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_agg");
+        hasher.update(self.domain);
+        for a in announcements {
+            hasher.update(a);
+        }
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Disclosure challenge, binding the revealed indices
+    fn compute_disclosure_challenge(
+        &self,
+        commitment: &Commitment,
+        revealed_indices: &[usize],
+        aggregate_announcement: &[u8; 32],
+    ) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_challenge");
+        hasher.update(self.domain);
+        hasher.update(commitment.as_bytes());
+        hasher.update(encode_indices(revealed_indices));
+        hasher.update(aggregate_announcement);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Per-attribute response `s_i = H(domain || k_i || c || m_i)`
+    fn compute_attr_response(&self, opening: &[u8; 32], challenge: &[u8; 32], value: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_response");
+        hasher.update(self.domain);
+        hasher.update(opening);
+        hasher.update(challenge);
+        hasher.update(value);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Aggregate verification tag over all responses
+    fn compute_disclosure_tag(
+        &self,
+        challenge: &[u8; 32],
+        aggregate_announcement: &[u8; 32],
+        responses: &[[u8; 32]],
+        commitment: &Commitment,
+    ) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_tag");
+        hasher.update(self.domain);
+        hasher.update(challenge);
+        hasher.update(aggregate_announcement);
+        for s in responses {
+            hasher.update(s);
+        }
+        hasher.update(commitment.as_bytes());
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
     /// Compute the nullifier for an exit note
     fn compute_nullifier(&self, note_id: &[u8; 32], owner_secret: &[u8; 32]) -> [u8; 32] {
         let mut hasher = Keccak256::new();
@@ -234,6 +659,8 @@ impl ProofGenerator {
         commitment: &Commitment,
         nullifier: &[u8; 32],
         announcement: &[u8; 32],
+        nonce: &[u8; 32],
+        memo_commitment: &[u8; 32],
     ) -> [u8; 32] {
         let mut hasher = Keccak256::new();
         hasher.update(b"voile_challenge");
@@ -241,8 +668,10 @@ impl ProofGenerator {
         hasher.update(commitment.as_bytes());
         hasher.update(nullifier);
         hasher.update(announcement);
+        hasher.update(nonce);
+        hasher.update(memo_commitment);
         let result = hasher.finalize();
-        
+
         let mut challenge = [0u8; 32];
         challenge.copy_from_slice(&result);
         challenge
@@ -306,8 +735,15 @@ impl Default for ProofGenerator {
 pub struct ProofVerifier {
     /// Domain separator (must match the generator's domain)
     domain: [u8; 32],
-    /// Set of used nullifiers (to prevent double-spending)
-    used_nullifiers: std::collections::HashSet<[u8; 32]>,
+    /// Accumulator of spent nullifiers (to prevent double-spending)
+    ///
+    /// Backed by a sparse Merkle tree so the spent set has a succinct on-chain
+    /// root and supports membership / non-membership proofs.
+    nullifiers: crate::smt::SparseMerkleTree,
+    /// Nonces issued by this verifier and not yet consumed
+    outstanding_nonces: std::collections::HashSet<[u8; 32]>,
+    /// Issuer public key for checking anonymous authorization credentials
+    issuer: Option<crate::credential::IssuerPublicKey>,
 }
 
 impl ProofVerifier {
@@ -320,16 +756,41 @@ impl ProofVerifier {
         hasher.update(b"voile_proof_domain");
         hasher.update(domain);
         let result = hasher.finalize();
-        
+
         let mut domain_hash = [0u8; 32];
         domain_hash.copy_from_slice(&result);
-        
+
         Self {
             domain: domain_hash,
-            used_nullifiers: std::collections::HashSet::new(),
+            nullifiers: crate::smt::SparseMerkleTree::new(),
+            outstanding_nonces: std::collections::HashSet::new(),
+            issuer: None,
         }
     }
 
+    /// Create a verifier that also checks anonymous authorization credentials
+    ///
+    /// # Arguments
+    /// * `domain` - A unique identifier for this proof domain
+    /// * `issuer` - The public key of the credential issuer to trust
+    pub fn with_issuer(domain: &[u8], issuer: crate::credential::IssuerPublicKey) -> Self {
+        let mut verifier = Self::new(domain);
+        verifier.issuer = Some(issuer);
+        verifier
+    }
+
+    /// Issue a fresh challenge nonce for an interactive verification
+    ///
+    /// The returned nonce is recorded as outstanding; a proof must fold it into
+    /// its challenge and present it to [`verify_with_nonce`](Self::verify_with_nonce),
+    /// which consumes it so it cannot be used twice.
+    pub fn generate_challenge_nonce(&mut self) -> [u8; 32] {
+        let mut nonce = [0u8; 32];
+        rand::thread_rng().fill(&mut nonce);
+        self.outstanding_nonces.insert(nonce);
+        nonce
+    }
+
     /// Verify an exit proof
     ///
     /// This checks that:
@@ -343,19 +804,40 @@ impl ProofVerifier {
     /// # Returns
     /// Result indicating success or the verification error
     pub fn verify(&self, proof: &ExitProof) -> Result<()> {
+        // The nonce-free API verifies against a zero nonce.
+        self.verify_against_nonce(proof, &[0u8; 32])
+    }
+
+    /// Verify a proof bound to a nonce previously issued by this verifier
+    ///
+    /// Rejects a stale or unknown nonce and consumes the nonce on use, so the
+    /// same proof cannot be replayed in a later session.
+    pub fn verify_with_nonce(&mut self, proof: &ExitProof, nonce: &[u8; 32]) -> Result<()> {
+        if !self.outstanding_nonces.contains(nonce) {
+            return Err(VoileError::ProofVerificationFailed(
+                "Unknown or stale challenge nonce".to_string()
+            ));
+        }
+        self.verify_against_nonce(proof, nonce)?;
+        self.outstanding_nonces.remove(nonce);
+        Ok(())
+    }
+
+    /// Shared verification path against a given nonce
+    fn verify_against_nonce(&self, proof: &ExitProof, nonce: &[u8; 32]) -> Result<()> {
         // Check if nullifier has been used
-        if self.used_nullifiers.contains(&proof.nullifier) {
+        if self.nullifiers.contains(&proof.nullifier) {
             return Err(VoileError::ProofVerificationFailed(
                 "Nullifier already used".to_string()
             ));
         }
-        
+
         // Verify basic proof structure
         self.verify_basic_structure(proof)?;
-        
+
         // Verify the cryptographic proof
-        self.verify_proof_cryptography(proof)?;
-        
+        self.verify_proof_cryptography(proof, nonce)?;
+
         Ok(())
     }
 
@@ -398,14 +880,17 @@ impl ProofVerifier {
     /// 1. Recomputes the challenge from public values
     /// 2. Recomputes the expected verification tag
     /// 3. Checks if the provided verification tag matches
-    fn verify_proof_cryptography(&self, proof: &ExitProof) -> Result<()> {
-        // Recompute the challenge from public values
+    fn verify_proof_cryptography(&self, proof: &ExitProof, nonce: &[u8; 32]) -> Result<()> {
+        // Recompute the challenge from public values, binding any memo.
+        let memo_comm = memo_commitment(&self.domain, &proof.memo_ciphertext);
         let challenge = self.compute_challenge(
             &proof.commitment,
             &proof.nullifier,
             &proof.announcement,
+            nonce,
+            &memo_comm,
         );
-        
+
         // Recompute the expected verification tag
         let expected_tag = self.compute_verification_tag(
             &proof.response,
@@ -431,6 +916,8 @@ impl ProofVerifier {
         commitment: &Commitment,
         nullifier: &[u8; 32],
         announcement: &[u8; 32],
+        nonce: &[u8; 32],
+        memo_commitment: &[u8; 32],
     ) -> [u8; 32] {
         let mut hasher = Keccak256::new();
         hasher.update(b"voile_challenge");
@@ -438,8 +925,10 @@ impl ProofVerifier {
         hasher.update(commitment.as_bytes());
         hasher.update(nullifier);
         hasher.update(announcement);
+        hasher.update(nonce);
+        hasher.update(memo_commitment);
         let result = hasher.finalize();
-        
+
         let mut challenge = [0u8; 32];
         challenge.copy_from_slice(&result);
         challenge
@@ -469,17 +958,244 @@ impl ProofVerifier {
         tag
     }
 
+    /// Verify a selective-disclosure proof against the revealed attributes
+    ///
+    /// Recomputes the challenge over the announcement and the disclosed
+    /// attribute values, checks the aggregate verification tag, and confirms
+    /// each revealed value opens its per-attribute announcement and response.
+    /// The verifier learns only the revealed fields.
+    pub fn verify_disclosed(
+        &self,
+        proof: &DisclosureProof,
+        revealed: &[RevealedAttribute],
+    ) -> Result<()> {
+        if proof.responses.len() != proof.announcements.len() {
+            return Err(VoileError::ProofVerificationFailed(
+                "Mismatched announcement/response counts".to_string()
+            ));
+        }
+        let n = proof.announcements.len();
+
+        // Recompute the aggregate announcement.
+        let aggregate = self.compute_aggregate_announcement(&proof.announcements);
+        if aggregate != proof.aggregate_announcement {
+            return Err(VoileError::ProofVerificationFailed(
+                "Aggregate announcement mismatch".to_string()
+            ));
+        }
+
+        // Recompute the challenge from the revealed indices.
+        let revealed_indices: Vec<usize> = revealed.iter().map(|r| r.index).collect();
+        let challenge = self.compute_disclosure_challenge(
+            &proof.commitment,
+            &revealed_indices,
+            &proof.aggregate_announcement,
+        );
+
+        // Recompute and check the aggregate verification tag.
+        let tag = self.compute_disclosure_tag(
+            &challenge,
+            &proof.aggregate_announcement,
+            &proof.responses,
+            &proof.commitment,
+        );
+        if tag != proof.verification_tag {
+            return Err(VoileError::ProofVerificationFailed(
+                "Verification tag mismatch".to_string()
+            ));
+        }
+
+        // Check each revealed attribute opens its announcement and response.
+        for r in revealed {
+            if r.index >= n {
+                return Err(VoileError::ProofVerificationFailed(
+                    format!("Revealed index {} out of range", r.index)
+                ));
+            }
+            if self.compute_attr_announcement(r.index, &r.opening) != proof.announcements[r.index] {
+                return Err(VoileError::ProofVerificationFailed(
+                    "Revealed attribute announcement mismatch".to_string()
+                ));
+            }
+            if self.compute_attr_response(&r.opening, &challenge, &r.value) != proof.responses[r.index] {
+                return Err(VoileError::ProofVerificationFailed(
+                    "Revealed attribute response mismatch".to_string()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Per-attribute announcement (same as prover)
+    fn compute_attr_announcement(&self, index: usize, opening: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_ann");
+        hasher.update(self.domain);
+        hasher.update((index as u32).to_le_bytes());
+        hasher.update(opening);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Aggregate announcement (same as prover)
+    fn compute_aggregate_announcement(&self, announcements: &[[u8; 32]]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_agg");
+        hasher.update(self.domain);
+        for a in announcements {
+            hasher.update(a);
+        }
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Disclosure challenge (same as prover)
+    fn compute_disclosure_challenge(
+        &self,
+        commitment: &Commitment,
+        revealed_indices: &[usize],
+        aggregate_announcement: &[u8; 32],
+    ) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_challenge");
+        hasher.update(self.domain);
+        hasher.update(commitment.as_bytes());
+        hasher.update(encode_indices(revealed_indices));
+        hasher.update(aggregate_announcement);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Per-attribute response (same as prover)
+    fn compute_attr_response(&self, opening: &[u8; 32], challenge: &[u8; 32], value: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_response");
+        hasher.update(self.domain);
+        hasher.update(opening);
+        hasher.update(challenge);
+        hasher.update(value);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Aggregate verification tag (same as prover)
+    fn compute_disclosure_tag(
+        &self,
+        challenge: &[u8; 32],
+        aggregate_announcement: &[u8; 32],
+        responses: &[[u8; 32]],
+        commitment: &Commitment,
+    ) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_attr_tag");
+        hasher.update(self.domain);
+        hasher.update(challenge);
+        hasher.update(aggregate_announcement);
+        for s in responses {
+            hasher.update(s);
+        }
+        hasher.update(commitment.as_bytes());
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hasher.finalize());
+        out
+    }
+
+    /// Verify a range proof on an exit amount
+    ///
+    /// Confirms the committed amount lies in `[0, 2^64)` and rejects a proof
+    /// whose bit count does not match the declared 64-bit width.
+    pub fn verify_range(&self, proof: &crate::range::RangeProof) -> Result<()> {
+        crate::range::verify(&self.domain, proof)
+    }
+
+    /// Verify an exit proof together with its anonymous-credential proof
+    ///
+    /// Runs the standard exit-proof checks and then confirms the prover holds a
+    /// valid issuer credential over the same commitment, bound to this proof's
+    /// transcript. Requires the verifier to have been created with
+    /// [`with_issuer`](Self::with_issuer).
+    pub fn verify_credentialed(
+        &self,
+        proof: &ExitProof,
+        credential_proof: &crate::credential::CredentialProof,
+    ) -> Result<()> {
+        let issuer = self.issuer.as_ref().ok_or_else(|| {
+            VoileError::ProofVerificationFailed("No issuer key configured".to_string())
+        })?;
+        self.verify(proof)?;
+        crate::credential::verify(
+            &self.domain,
+            issuer,
+            credential_proof,
+            proof.commitment.as_bytes(),
+            &proof.nullifier,
+            &proof.announcement,
+        )
+    }
+
     /// Mark a nullifier as used (call after successful verification and execution)
     ///
+    /// Updates the accumulator root to commit to the newly spent nullifier.
+    ///
     /// # Arguments
     /// * `nullifier` - The nullifier to mark as used
     pub fn mark_nullifier_used(&mut self, nullifier: [u8; 32]) {
-        self.used_nullifiers.insert(nullifier);
+        self.nullifiers.insert(nullifier);
     }
 
     /// Check if a nullifier has been used
     pub fn is_nullifier_used(&self, nullifier: &[u8; 32]) -> bool {
-        self.used_nullifiers.contains(nullifier)
+        self.nullifiers.contains(nullifier)
+    }
+
+    /// The current commitment to the spent-nullifier set
+    ///
+    /// Publishing this root lets light clients verify double-spend status
+    /// statelessly via [`verify_stateless`](Self::verify_stateless).
+    pub fn nullifier_root(&self) -> [u8; 32] {
+        self.nullifiers.root()
+    }
+
+    /// Prove that `nullifier` has been spent (is in the accumulator)
+    pub fn prove_nullifier_inclusion(&self, nullifier: &[u8; 32]) -> crate::smt::MerkleProof {
+        self.nullifiers.prove_inclusion(nullifier)
+    }
+
+    /// Prove that `nullifier` has not been spent (is absent from the accumulator)
+    pub fn prove_nullifier_absence(&self, nullifier: &[u8; 32]) -> crate::smt::MerkleProof {
+        self.nullifiers.prove_absence(nullifier)
+    }
+
+    /// Verify a proof statelessly against a published root and absence proof
+    ///
+    /// A light client that holds only the accumulator root can check a proof
+    /// without the full spent-set: the caller supplies a non-membership proof
+    /// for the proof's nullifier against `root`, and this confirms both the
+    /// cryptographic proof and that the nullifier is still unspent.
+    pub fn verify_stateless(
+        &self,
+        proof: &ExitProof,
+        root: &[u8; 32],
+        absence: &crate::smt::MerkleProof,
+    ) -> Result<()> {
+        if absence.is_member() || absence.key() != &proof.nullifier {
+            return Err(VoileError::ProofVerificationFailed(
+                "Absence proof does not match the proof's nullifier".to_string()
+            ));
+        }
+        if !absence.verify(root) {
+            return Err(VoileError::ProofVerificationFailed(
+                "Nullifier absence proof failed against root".to_string()
+            ));
+        }
+        self.verify_basic_structure(proof)?;
+        self.verify_proof_cryptography(proof, &[0u8; 32])?;
+        Ok(())
     }
 }
 
@@ -660,6 +1376,289 @@ mod tests {
         assert!(matches!(result.unwrap_err(), VoileError::ProofVerificationFailed(_)));
     }
 
+    #[test]
+    fn test_nonce_bound_proof_roundtrip() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let mut verifier = ProofVerifier::default();
+        let nonce = verifier.generate_challenge_nonce();
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate_with_nonce(&note, &owner_secret, &nonce).unwrap();
+
+        assert!(verifier.verify_with_nonce(&proof, &nonce).is_ok());
+    }
+
+    #[test]
+    fn test_proof_fails_under_different_nonce() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let mut verifier = ProofVerifier::default();
+        let nonce = verifier.generate_challenge_nonce();
+        let wrong = verifier.generate_challenge_nonce();
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate_with_nonce(&note, &owner_secret, &nonce).unwrap();
+
+        // Built for `nonce`, presented with `wrong` → challenge mismatch.
+        assert!(verifier.verify_with_nonce(&proof, &wrong).is_err());
+    }
+
+    #[test]
+    fn test_unknown_nonce_rejected() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate_with_nonce(&note, &owner_secret, &[5u8; 32]).unwrap();
+
+        let mut verifier = ProofVerifier::default();
+        // The verifier never issued this nonce.
+        assert!(verifier.verify_with_nonce(&proof, &[5u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_nonce_consumed_after_use() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let mut verifier = ProofVerifier::default();
+        let nonce = verifier.generate_challenge_nonce();
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate_with_nonce(&note, &owner_secret, &nonce).unwrap();
+
+        assert!(verifier.verify_with_nonce(&proof, &nonce).is_ok());
+        // Replaying the same proof/nonce fails: the nonce was consumed.
+        assert!(verifier.verify_with_nonce(&proof, &nonce).is_err());
+    }
+
+    #[test]
+    fn test_selective_disclosure_reveals_subset() {
+        let note = create_test_note();
+        let generator = ProofGenerator::default();
+
+        // Reveal only the terms attribute (index 2).
+        let (proof, revealed) = generator.generate_disclosed(&note, &[2]).unwrap();
+
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify_disclosed(&proof, &revealed).is_ok());
+        assert_eq!(revealed.len(), 1);
+        assert_eq!(revealed[0].value, note.terms().to_bytes());
+    }
+
+    #[test]
+    fn test_disclosure_tampered_value_fails() {
+        let note = create_test_note();
+        let generator = ProofGenerator::default();
+        let (proof, mut revealed) = generator.generate_disclosed(&note, &[0]).unwrap();
+
+        // Claim a different amount for the revealed attribute.
+        revealed[0].value = 9999u64.to_le_bytes().to_vec();
+
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify_disclosed(&proof, &revealed).is_err());
+    }
+
+    #[test]
+    fn test_disclosure_empty_reveals_nothing() {
+        let note = create_test_note();
+        let generator = ProofGenerator::default();
+        let (proof, revealed) = generator.generate_disclosed(&note, &[]).unwrap();
+
+        let verifier = ProofVerifier::default();
+        assert!(revealed.is_empty());
+        assert!(verifier.verify_disclosed(&proof, &revealed).is_ok());
+    }
+
+    #[test]
+    fn test_range_proof_accompanies_exit_proof() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let (proof, range) = generator.generate_with_range(&note, &owner_secret).unwrap();
+
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify(&proof).is_ok());
+        assert!(verifier.verify_range(&range).is_ok());
+    }
+
+    #[test]
+    fn test_range_proof_rejected_cross_domain() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::new(b"chain_1");
+        let (_, range) = generator.generate_with_range(&note, &owner_secret).unwrap();
+
+        // A verifier on a different domain must reject the range proof.
+        let verifier = ProofVerifier::new(b"chain_2");
+        assert!(verifier.verify_range(&range).is_err());
+    }
+
+    #[test]
+    fn test_memo_proof_verifies_and_roundtrips() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+        let memo = [7u8; 512];
+
+        let generator = ProofGenerator::default();
+        let proof = generator
+            .generate_with_memo(&note, &owner_secret, &memo, b"recipient")
+            .unwrap();
+
+        assert!(proof.memo_ciphertext().is_some());
+
+        // Serialized memo proof is longer than the legacy 160 bytes but parses.
+        let bytes = proof.to_bytes();
+        assert!(bytes.len() > 160);
+        let recovered = ExitProof::from_bytes(&bytes).unwrap();
+
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify(&recovered).is_ok());
+    }
+
+    #[test]
+    fn test_memo_tamper_fails_verification() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+        let memo = [7u8; 512];
+
+        let generator = ProofGenerator::default();
+        let proof = generator
+            .generate_with_memo(&note, &owner_secret, &memo, b"recipient")
+            .unwrap();
+
+        // Flip a byte inside the memo ciphertext; the memo commitment no longer
+        // matches the one bound into the challenge.
+        let mut bytes = proof.to_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let tampered = ExitProof::from_bytes(&bytes).unwrap();
+
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_legacy_proof_still_parses() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate(&note, &owner_secret).unwrap();
+
+        // A memo-free proof keeps the 160-byte layout.
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), 160);
+        assert!(ExitProof::from_bytes(&bytes).unwrap().memo_ciphertext().is_none());
+    }
+
+    #[test]
+    fn test_nullifier_root_changes_on_spend() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate(&note, &owner_secret).unwrap();
+
+        let mut verifier = ProofVerifier::default();
+        let before = verifier.nullifier_root();
+        verifier.mark_nullifier_used(*proof.nullifier());
+        assert_ne!(before, verifier.nullifier_root());
+        assert!(verifier.prove_nullifier_inclusion(proof.nullifier()).is_member());
+    }
+
+    #[test]
+    fn test_stateless_verification_against_root() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate(&note, &owner_secret).unwrap();
+
+        let verifier = ProofVerifier::default();
+        let root = verifier.nullifier_root();
+        let absence = verifier.prove_nullifier_absence(proof.nullifier());
+
+        // An unspent nullifier verifies statelessly against the published root.
+        assert!(verifier.verify_stateless(&proof, &root, &absence).is_ok());
+    }
+
+    #[test]
+    fn test_stateless_verification_rejects_spent_nullifier() {
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let generator = ProofGenerator::default();
+        let proof = generator.generate(&note, &owner_secret).unwrap();
+
+        let mut verifier = ProofVerifier::default();
+        // Stale absence proof captured before the nullifier was spent.
+        let absence = verifier.prove_nullifier_absence(proof.nullifier());
+        verifier.mark_nullifier_used(*proof.nullifier());
+
+        // Against the new root the absence proof no longer holds.
+        let result = verifier.verify_stateless(&proof, &verifier.nullifier_root(), &absence);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_credentialed_proof_verifies() {
+        use crate::credential::{CredentialRequest, Issuer};
+
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let pk = issuer.public_key();
+
+        // Issue a credential over the note's commitment via blind signing.
+        let commitment = note.commitment();
+        let (nonce, r_prime) = issuer.commit_nonce();
+        let (request, blinded) =
+            CredentialRequest::new(&pk, &r_prime, commitment.as_bytes()).unwrap();
+        let s_prime = issuer.blind_sign(&nonce, &blinded).unwrap();
+        let credential = request.unblind(&pk, &s_prime).unwrap();
+
+        let generator = ProofGenerator::default();
+        let (proof, cred_proof) = generator
+            .generate_with_credential(&note, &owner_secret, &credential)
+            .unwrap();
+
+        let verifier = ProofVerifier::with_issuer(b"voile_mainnet", pk);
+        assert!(verifier.verify_credentialed(&proof, &cred_proof).is_ok());
+    }
+
+    #[test]
+    fn test_credentialed_proof_requires_issuer() {
+        use crate::credential::{CredentialRequest, Issuer};
+
+        let note = create_test_note();
+        let owner_secret = [123u8; 32];
+
+        let issuer = Issuer::from_secret(&[1u8; 32]);
+        let pk = issuer.public_key();
+        let commitment = note.commitment();
+        let (nonce, r_prime) = issuer.commit_nonce();
+        let (request, blinded) =
+            CredentialRequest::new(&pk, &r_prime, commitment.as_bytes()).unwrap();
+        let s_prime = issuer.blind_sign(&nonce, &blinded).unwrap();
+        let credential = request.unblind(&pk, &s_prime).unwrap();
+
+        let generator = ProofGenerator::default();
+        let (proof, cred_proof) = generator
+            .generate_with_credential(&note, &owner_secret, &credential)
+            .unwrap();
+
+        // A verifier without an issuer key cannot check the credential.
+        let verifier = ProofVerifier::default();
+        assert!(verifier.verify_credentialed(&proof, &cred_proof).is_err());
+    }
+
     #[test]
     fn test_serialized_proof_verifies() {
         let note = create_test_note();
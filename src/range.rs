@@ -0,0 +1,294 @@
+//! Range proofs for exit amounts in Voile Protocol
+//!
+//! When an amount is hidden inside a value commitment, a malicious prover could
+//! encode a negative or overflowing value and mint funds. A [`RangeProof`]
+//! attests that the committed amount `v` satisfies `0 <= v < 2^64` by
+//! committing to each bit, proving every bit opens to 0 or 1 with a two-branch
+//! OR proof, and proving the weighted bit sum equals the value commitment.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::Rng;
+use sha3::{Digest, Keccak256};
+use crate::commitment::pedersen_h;
+use crate::{Result, VoileError};
+
+/// The fixed bit width proven by a range proof
+pub const RANGE_BITS: usize = 64;
+
+/// The OR proof that a single bit commitment opens to 0 or 1
+#[derive(Clone)]
+struct BitProof {
+    commitment: [u8; 32],
+    a0: [u8; 32],
+    a1: [u8; 32],
+    e0: [u8; 32],
+    s0: [u8; 32],
+    s1: [u8; 32],
+}
+
+/// A bit-decomposition range proof that a committed amount is in `[0, 2^64)`
+#[derive(Clone)]
+pub struct RangeProof {
+    /// The value commitment `V = v*G + r*H`
+    value_commitment: [u8; 32],
+    /// One OR proof per bit
+    bits: Vec<BitProof>,
+    /// Schnorr announcement for the aggregation proof
+    agg_announcement: [u8; 32],
+    /// Schnorr response for the aggregation proof
+    agg_response: [u8; 32],
+}
+
+impl RangeProof {
+    /// The value commitment this proof is about
+    pub fn value_commitment(&self) -> &[u8; 32] {
+        &self.value_commitment
+    }
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn scalar_from_seed(label: &[u8], seed: &[u8; 32]) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(label);
+    hasher.update(seed);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+fn point_bytes(p: &RistrettoPoint) -> [u8; 32] {
+    p.compress().to_bytes()
+}
+
+fn parse_point(bytes: &[u8; 32]) -> Result<RistrettoPoint> {
+    CompressedRistretto(*bytes)
+        .decompress()
+        .ok_or_else(|| VoileError::ProofVerificationFailed("Invalid range proof point".to_string()))
+}
+
+fn parse_scalar(bytes: &[u8; 32]) -> Result<Scalar> {
+    Scalar::from_canonical_bytes(*bytes)
+        .into_option()
+        .ok_or_else(|| VoileError::ProofVerificationFailed("Invalid range proof scalar".to_string()))
+}
+
+/// Recompute the global bit-proof challenge, binding the bit width
+fn bit_challenge(domain: &[u8; 32], bits: &[BitProof]) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_range_bit_challenge");
+    hasher.update(domain);
+    hasher.update((RANGE_BITS as u32).to_le_bytes());
+    for b in bits {
+        hasher.update(b.commitment);
+        hasher.update(b.a0);
+        hasher.update(b.a1);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+/// Recompute the aggregation-proof challenge
+fn agg_challenge(domain: &[u8; 32], value_commitment: &[u8; 32], announcement: &[u8; 32]) -> Scalar {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_range_agg_challenge");
+    hasher.update(domain);
+    hasher.update((RANGE_BITS as u32).to_le_bytes());
+    hasher.update(value_commitment);
+    hasher.update(announcement);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order(out)
+}
+
+/// Produce a range proof for `amount`
+///
+/// `seed` deterministically derives the value-commitment blinding so the prover
+/// can reconstruct the proof; the per-bit blindings are sampled fresh.
+pub fn prove(domain: &[u8; 32], amount: u64, seed: &[u8; 32]) -> RangeProof {
+    let g = RISTRETTO_BASEPOINT_POINT;
+    let h = pedersen_h();
+
+    let r = scalar_from_seed(b"voile_range_vblind", seed);
+    let value_commitment = g * Scalar::from(amount) + h * r;
+
+    // Per-bit commitments and OR-proof first pass.
+    struct Pending {
+        branch: u8,
+        k: Scalar,
+        sim_e: Scalar,
+        sim_s: Scalar,
+        r_bit: Scalar,
+        commitment: RistrettoPoint,
+        a0: RistrettoPoint,
+        a1: RistrettoPoint,
+    }
+
+    let mut pending = Vec::with_capacity(RANGE_BITS);
+    let mut weighted_blinding = Scalar::ZERO;
+
+    for i in 0..RANGE_BITS {
+        let bit = (amount >> i) & 1;
+        let r_bit = random_scalar();
+        let commitment = g * Scalar::from(bit) + h * r_bit;
+        weighted_blinding += Scalar::from(1u64 << i) * r_bit;
+
+        let k = random_scalar();
+        let sim_e = random_scalar();
+        let sim_s = random_scalar();
+
+        let (a0, a1, branch) = if bit == 0 {
+            // Branch 0 real, branch 1 simulated (statement point C - G).
+            let a0 = h * k;
+            let a1 = h * sim_s - (commitment - g) * sim_e;
+            (a0, a1, 0)
+        } else {
+            // Branch 1 real, branch 0 simulated (statement point C).
+            let a1 = h * k;
+            let a0 = h * sim_s - commitment * sim_e;
+            (a0, a1, 1)
+        };
+
+        pending.push(Pending { branch, k, sim_e, sim_s, r_bit, commitment, a0, a1 });
+    }
+
+    // Build skeleton bit proofs to compute the global challenge.
+    let mut bits: Vec<BitProof> = pending
+        .iter()
+        .map(|p| BitProof {
+            commitment: point_bytes(&p.commitment),
+            a0: point_bytes(&p.a0),
+            a1: point_bytes(&p.a1),
+            e0: [0u8; 32],
+            s0: [0u8; 32],
+            s1: [0u8; 32],
+        })
+        .collect();
+
+    let c = bit_challenge(domain, &bits);
+
+    // Second pass: close the real branch.
+    for (p, bp) in pending.iter().zip(bits.iter_mut()) {
+        let (e0, s0, e1, s1) = if p.branch == 0 {
+            let e0 = c - p.sim_e;
+            let s0 = p.k + e0 * p.r_bit;
+            (e0, s0, p.sim_e, p.sim_s)
+        } else {
+            let e1 = c - p.sim_e;
+            let s1 = p.k + e1 * p.r_bit;
+            (p.sim_e, p.sim_s, e1, s1)
+        };
+        bp.e0 = e0.to_bytes();
+        bp.s0 = s0.to_bytes();
+        bp.s1 = s1.to_bytes();
+        let _ = e1; // e1 is recomputed by the verifier as c - e0
+    }
+
+    // Aggregation: V - sum 2^i C_i = (r - weighted_blinding) * H.
+    let delta = r - weighted_blinding;
+    let k_agg = random_scalar();
+    let announcement = h * k_agg;
+    let c_agg = agg_challenge(domain, &point_bytes(&value_commitment), &point_bytes(&announcement));
+    let z = k_agg + c_agg * delta;
+
+    RangeProof {
+        value_commitment: point_bytes(&value_commitment),
+        bits,
+        agg_announcement: point_bytes(&announcement),
+        agg_response: z.to_bytes(),
+    }
+}
+
+/// Verify a range proof
+pub fn verify(domain: &[u8; 32], proof: &RangeProof) -> Result<()> {
+    if proof.bits.len() != RANGE_BITS {
+        return Err(VoileError::ProofVerificationFailed(
+            format!("Range proof has {} bits, expected {}", proof.bits.len(), RANGE_BITS)
+        ));
+    }
+
+    let g = RISTRETTO_BASEPOINT_POINT;
+    let h = pedersen_h();
+    let c = bit_challenge(domain, &proof.bits);
+
+    let mut weighted_sum = RistrettoPoint::default();
+    for (i, b) in proof.bits.iter().enumerate() {
+        let commitment = parse_point(&b.commitment)?;
+        let a0 = parse_point(&b.a0)?;
+        let a1 = parse_point(&b.a1)?;
+        let e0 = parse_scalar(&b.e0)?;
+        let s0 = parse_scalar(&b.s0)?;
+        let s1 = parse_scalar(&b.s1)?;
+        let e1 = c - e0;
+
+        // Branch 0: C opens to 0 (C = r*H). Branch 1: C - G opens to 0.
+        if h * s0 != a0 + commitment * e0 {
+            return Err(VoileError::ProofVerificationFailed(
+                format!("Bit {} branch-0 check failed", i)
+            ));
+        }
+        if h * s1 != a1 + (commitment - g) * e1 {
+            return Err(VoileError::ProofVerificationFailed(
+                format!("Bit {} branch-1 check failed", i)
+            ));
+        }
+
+        weighted_sum += commitment * Scalar::from(1u64 << i);
+    }
+
+    // Aggregation: z*H == T + c_agg*(V - sum 2^i C_i).
+    let value_commitment = parse_point(&proof.value_commitment)?;
+    let announcement = parse_point(&proof.agg_announcement)?;
+    let z = parse_scalar(&proof.agg_response)?;
+    let c_agg = agg_challenge(domain, &proof.value_commitment, &proof.agg_announcement);
+    let target = value_commitment - weighted_sum;
+
+    if h * z != announcement + target * c_agg {
+        return Err(VoileError::ProofVerificationFailed(
+            "Range aggregation check failed".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn test_range_proof_verifies() {
+        let proof = prove(&DOMAIN, 123_456_789, &[1u8; 32]);
+        assert!(verify(&DOMAIN, &proof).is_ok());
+    }
+
+    #[test]
+    fn test_range_proof_boundaries() {
+        for amount in [0u64, 1, u64::MAX] {
+            let proof = prove(&DOMAIN, amount, &[2u8; 32]);
+            assert!(verify(&DOMAIN, &proof).is_ok(), "amount {} failed", amount);
+        }
+    }
+
+    #[test]
+    fn test_range_proof_wrong_domain_fails() {
+        let proof = prove(&DOMAIN, 42, &[3u8; 32]);
+        assert!(verify(&[8u8; 32], &proof).is_err());
+    }
+
+    #[test]
+    fn test_range_proof_rejects_wrong_bit_count() {
+        let mut proof = prove(&DOMAIN, 42, &[3u8; 32]);
+        proof.bits.pop();
+        assert!(verify(&DOMAIN, &proof).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+//! Sparse Merkle accumulator for the nullifier set
+//!
+//! `ProofVerifier` needs a commitment to the spent-nullifier set that can be
+//! published on-chain, synced between nodes, and queried with a succinct proof.
+//! A [`SparseMerkleTree`] keyed by the 256-bit nullifier provides exactly that:
+//! the root is a single 32-byte commitment, and [`MerkleProof`] witnesses both
+//! membership (a spent nullifier) and non-membership (an unspent one).
+//!
+//! The tree has one leaf per possible 256-bit key, so the vast majority of
+//! subtrees are empty. Their hashes are identical per level and precomputed
+//! once in the `empty` table, letting proofs omit default siblings entirely and
+//! the node map store only the non-default nodes.
+
+use std::collections::{HashMap, HashSet};
+use sha3::{Digest, Keccak256};
+
+/// The key width in bits, and therefore the tree depth
+const KEY_BITS: usize = 256;
+
+/// Hash of an empty leaf, the base of the `empty` table
+fn empty_leaf() -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_smt_empty");
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// Hash of an occupied leaf for `key`
+fn leaf_hash(key: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_smt_leaf");
+    hasher.update(key);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// Hash of an internal node from its two children
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"voile_smt_node");
+    hasher.update(left);
+    hasher.update(right);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// The `i`-th bit of `key`, counting from the most-significant bit (`i = 0`)
+fn bit(key: &[u8; 32], i: usize) -> u8 {
+    (key[i / 8] >> (7 - (i % 8))) & 1
+}
+
+/// Zero every bit of `key` at index `depth` and beyond, yielding the canonical
+/// prefix that identifies a node at the given depth from the root
+fn prefix_at_depth(key: &[u8; 32], depth: usize) -> [u8; 32] {
+    let mut out = *key;
+    for i in depth..KEY_BITS {
+        out[i / 8] &= !(1 << (7 - (i % 8)));
+    }
+    out
+}
+
+/// Precompute the hash of an all-empty subtree at each height (0 = leaf)
+fn empty_table() -> Vec<[u8; 32]> {
+    let mut table = Vec::with_capacity(KEY_BITS + 1);
+    table.push(empty_leaf());
+    for h in 1..=KEY_BITS {
+        let child = table[h - 1];
+        table.push(node_hash(&child, &child));
+    }
+    table
+}
+
+/// A compressed authentication path for a single key
+///
+/// The sibling hashes are listed from the leaf upward, but default (empty)
+/// siblings are omitted; `present` is a 256-bit mask (MSB-first per byte) whose
+/// set bits mark the heights at which a non-default sibling is carried.
+#[derive(Clone, Debug)]
+pub struct MerkleProof {
+    /// The key this path is about
+    key: [u8; 32],
+    /// Whether the leaf is occupied (membership) or empty (non-membership)
+    member: bool,
+    /// Bitmask of heights carrying a non-default sibling
+    present: [u8; 32],
+    /// The non-default sibling hashes, leaf-upward in height order
+    siblings: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// The key this proof is about
+    pub fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// Whether this is a membership (`true`) or non-membership (`false`) proof
+    pub fn is_member(&self) -> bool {
+        self.member
+    }
+
+    /// Recompute the root implied by this proof and compare it to `root`
+    ///
+    /// A light verifier with only the root can decide double-spend status from
+    /// this alone.
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        let empty = empty_table();
+        let mut cur = if self.member { leaf_hash(&self.key) } else { empty[0] };
+        let mut next_sibling = 0;
+
+        for (h, &empty_h) in empty.iter().enumerate().take(KEY_BITS) {
+            let byte = h / 8;
+            let mask = 1u8 << (7 - (h % 8));
+            let sibling = if self.present[byte] & mask != 0 {
+                let s = match self.siblings.get(next_sibling) {
+                    Some(s) => *s,
+                    None => return false,
+                };
+                next_sibling += 1;
+                s
+            } else {
+                empty_h
+            };
+
+            let depth = KEY_BITS - h;
+            cur = if bit(&self.key, depth - 1) == 0 {
+                node_hash(&cur, &sibling)
+            } else {
+                node_hash(&sibling, &cur)
+            };
+        }
+
+        next_sibling == self.siblings.len() && cur == *root
+    }
+}
+
+/// A sparse Merkle tree over 256-bit keys, storing only non-default nodes
+pub struct SparseMerkleTree {
+    /// Non-default node hashes, keyed by `(height, prefix)`
+    nodes: HashMap<(u16, [u8; 32]), [u8; 32]>,
+    /// Occupied leaf keys, for fast membership queries
+    leaves: HashSet<[u8; 32]>,
+    /// Precomputed empty-subtree hash per height (0 = leaf)
+    empty: Vec<[u8; 32]>,
+    /// Current root
+    root: [u8; 32],
+}
+
+impl SparseMerkleTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        let empty = empty_table();
+        let root = empty[KEY_BITS];
+        Self {
+            nodes: HashMap::new(),
+            leaves: HashSet::new(),
+            empty,
+            root,
+        }
+    }
+
+    /// The current Merkle root
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Whether `key` is present in the tree
+    pub fn contains(&self, key: &[u8; 32]) -> bool {
+        self.leaves.contains(key)
+    }
+
+    /// Look up a node hash, falling back to the empty-subtree hash
+    fn node(&self, height: u16, prefix: &[u8; 32]) -> [u8; 32] {
+        self.nodes
+            .get(&(height, *prefix))
+            .copied()
+            .unwrap_or(self.empty[height as usize])
+    }
+
+    /// Insert `key`, recomputing the root along its path
+    pub fn insert(&mut self, key: [u8; 32]) {
+        if !self.leaves.insert(key) {
+            return;
+        }
+
+        let mut cur = leaf_hash(&key);
+        self.nodes.insert((0, key), cur);
+
+        for h in 0..KEY_BITS {
+            let depth = KEY_BITS - h;
+            let b = bit(&key, depth - 1);
+
+            // Sibling shares the top (depth - 1) bits and differs in bit depth-1.
+            let mut sibling_prefix = prefix_at_depth(&key, depth);
+            let idx = depth - 1;
+            sibling_prefix[idx / 8] ^= 1 << (7 - (idx % 8));
+            let sib = self.node(h as u16, &sibling_prefix);
+
+            cur = if b == 0 {
+                node_hash(&cur, &sib)
+            } else {
+                node_hash(&sib, &cur)
+            };
+
+            let parent_prefix = prefix_at_depth(&key, depth - 1);
+            self.nodes.insert(((h + 1) as u16, parent_prefix), cur);
+        }
+
+        self.root = cur;
+    }
+
+    /// Build the compressed authentication path for `key`
+    fn path(&self, key: &[u8; 32]) -> MerkleProof {
+        let member = self.leaves.contains(key);
+        let mut present = [0u8; 32];
+        let mut siblings = Vec::new();
+
+        for h in 0..KEY_BITS {
+            let depth = KEY_BITS - h;
+            let mut sibling_prefix = prefix_at_depth(key, depth);
+            let idx = depth - 1;
+            sibling_prefix[idx / 8] ^= 1 << (7 - (idx % 8));
+            let sib = self.node(h as u16, &sibling_prefix);
+
+            if sib != self.empty[h] {
+                present[h / 8] |= 1 << (7 - (h % 8));
+                siblings.push(sib);
+            }
+        }
+
+        MerkleProof { key: *key, member, present, siblings }
+    }
+
+    /// Produce a membership proof that `key` is present
+    pub fn prove_inclusion(&self, key: &[u8; 32]) -> MerkleProof {
+        self.path(key)
+    }
+
+    /// Produce a non-membership proof that `key` is absent
+    pub fn prove_absence(&self, key: &[u8; 32]) -> MerkleProof {
+        self.path(key)
+    }
+}
+
+impl Default for SparseMerkleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> [u8; 32] {
+        let mut k = [seed; 32];
+        k[0] = seed;
+        k[31] = seed.wrapping_add(1);
+        k
+    }
+
+    #[test]
+    fn test_empty_root_is_stable() {
+        let a = SparseMerkleTree::new();
+        let b = SparseMerkleTree::new();
+        assert_eq!(a.root(), b.root());
+    }
+
+    #[test]
+    fn test_insert_changes_root() {
+        let mut tree = SparseMerkleTree::new();
+        let before = tree.root();
+        tree.insert(key(1));
+        assert_ne!(before, tree.root());
+        assert!(tree.contains(&key(1)));
+    }
+
+    #[test]
+    fn test_inclusion_proof_verifies() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(key(1));
+        tree.insert(key(2));
+        let proof = tree.prove_inclusion(&key(1));
+        assert!(proof.is_member());
+        assert!(proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn test_absence_proof_verifies() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(key(1));
+        let proof = tree.prove_absence(&key(9));
+        assert!(!proof.is_member());
+        assert!(proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn test_absence_proof_fails_after_insertion() {
+        let mut tree = SparseMerkleTree::new();
+        let absence = tree.prove_absence(&key(5));
+        assert!(absence.verify(&tree.root()));
+
+        // Once the key is inserted the stale absence proof no longer matches.
+        tree.insert(key(5));
+        assert!(!absence.verify(&tree.root()));
+    }
+
+    #[test]
+    fn test_proof_rejected_under_wrong_root() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(key(1));
+        let proof = tree.prove_inclusion(&key(1));
+        assert!(!proof.verify(&[0u8; 32]));
+    }
+
+    #[test]
+    fn test_idempotent_insert() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(key(3));
+        let root = tree.root();
+        tree.insert(key(3));
+        assert_eq!(root, tree.root());
+    }
+}
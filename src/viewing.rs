@@ -0,0 +1,144 @@
+//! Viewing keys and trial-decryption scanning for Voile Protocol
+//!
+//! A viewing key lets a user (or a delegated watcher) scan a stream of
+//! [`EncryptedNote`]s and recover only the ones addressed to them, without the
+//! spend authority held by the full encryption key. This enables light-client
+//! wallet recovery and auditor scoping of exit activity.
+
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use sha3::{Digest, Keccak256};
+use crate::{EncryptedNote, ExitNote, Result, VoileError};
+
+/// The scope a viewing key is derived under
+///
+/// External-scoped keys detect notes received from others; internal-scoped
+/// keys detect change notes a wallet sends to itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Notes received from external parties
+    External,
+    /// Change notes sent to oneself
+    Internal,
+}
+
+impl Scope {
+    /// Domain-separation byte for this scope
+    fn tag(&self) -> u8 {
+        match self {
+            Scope::External => 0,
+            Scope::Internal => 1,
+        }
+    }
+}
+
+/// An incoming viewing key, able to detect and decrypt notes but not spend them
+#[derive(Clone)]
+pub struct IncomingViewingKey {
+    /// The key-agreement secret scalar
+    secret: [u8; 32],
+    /// The scope this key was derived under
+    scope: Scope,
+}
+
+impl IncomingViewingKey {
+    /// Derive an incoming viewing key from the owner key and a scope
+    pub fn from_owner(owner_key: &[u8; 32], scope: Scope) -> Result<Self> {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"voile_ivk");
+        hasher.update(owner_key);
+        hasher.update([scope.tag()]);
+        let mut secret = [0u8; 32];
+        secret.copy_from_slice(&hasher.finalize());
+
+        // Ensure the derived scalar is a valid secp256k1 secret key.
+        SecretKey::from_slice(&secret)
+            .map_err(|e| VoileError::InvalidKey(format!("Invalid viewing key: {}", e)))?;
+
+        Ok(Self { secret, scope })
+    }
+
+    /// The scope of this viewing key
+    pub fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// The public key a sender encrypts notes to for this viewing key
+    pub fn public_key(&self) -> Vec<u8> {
+        let secp = Secp256k1::new();
+        let sk = SecretKey::from_slice(&self.secret).expect("viewing key validated on construction");
+        PublicKey::from_secret_key(&secp, &sk).serialize().to_vec()
+    }
+
+    /// The raw key-agreement secret
+    fn secret(&self) -> &[u8; 32] {
+        &self.secret
+    }
+}
+
+/// Attempt to decrypt an encrypted note with an incoming viewing key
+///
+/// Succeeds (returning the note) only for the intended recipient, and fails
+/// cheaply with `None` otherwise.
+pub fn try_decrypt_with_ivk(encrypted: &EncryptedNote, ivk: &IncomingViewingKey) -> Option<ExitNote> {
+    let plaintext = encrypted.try_decrypt_with_secret(ivk.secret())?;
+    ExitNote::from_bytes(&plaintext).ok()
+}
+
+/// Scan a batch of encrypted notes, returning those addressed to `ivk`
+pub fn scan(notes: &[EncryptedNote], ivk: &IncomingViewingKey) -> Vec<ExitNote> {
+    notes
+        .iter()
+        .filter_map(|note| try_decrypt_with_ivk(note, ivk))
+        .collect()
+}
+
+/// Encrypt an exit note to an incoming viewing key for later scanning
+pub fn encrypt_to_ivk(note: &ExitNote, ivk: &IncomingViewingKey) -> Result<EncryptedNote> {
+    EncryptedNote::encrypt_to_recipient(&ivk.public_key(), &note.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exit_note::ExitTerms;
+
+    #[test]
+    fn test_scan_recovers_only_addressed_notes() {
+        let owner_a = [1u8; 32];
+        let owner_b = [2u8; 32];
+        let ivk_a = IncomingViewingKey::from_owner(&owner_a, Scope::External).unwrap();
+        let ivk_b = IncomingViewingKey::from_owner(&owner_b, Scope::External).unwrap();
+
+        let note_for_a = ExitNote::new(1000, owner_a, ExitTerms::Standard);
+        let note_for_b = ExitNote::new(2000, owner_b, ExitTerms::Immediate);
+
+        let stream = vec![
+            encrypt_to_ivk(&note_for_a, &ivk_a).unwrap(),
+            encrypt_to_ivk(&note_for_b, &ivk_b).unwrap(),
+        ];
+
+        let matched = scan(&stream, &ivk_a);
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].amount(), 1000);
+    }
+
+    #[test]
+    fn test_wrong_ivk_fails_cheaply() {
+        let ivk = IncomingViewingKey::from_owner(&[3u8; 32], Scope::External).unwrap();
+        let other = IncomingViewingKey::from_owner(&[4u8; 32], Scope::External).unwrap();
+
+        let note = ExitNote::new(500, [3u8; 32], ExitTerms::Standard);
+        let encrypted = encrypt_to_ivk(&note, &ivk).unwrap();
+
+        assert!(try_decrypt_with_ivk(&encrypted, &other).is_none());
+        assert!(try_decrypt_with_ivk(&encrypted, &ivk).is_some());
+    }
+
+    #[test]
+    fn test_scope_changes_key() {
+        let owner = [7u8; 32];
+        let external = IncomingViewingKey::from_owner(&owner, Scope::External).unwrap();
+        let internal = IncomingViewingKey::from_owner(&owner, Scope::Internal).unwrap();
+        assert_ne!(external.public_key(), internal.public_key());
+    }
+}